@@ -0,0 +1,310 @@
+use crate::{
+    board::{Board, Cell},
+    candidates::Candidates,
+    house::House,
+    step::{cell_name, cell_names, value_names, Step},
+    subsets::make_combinations_from_valid_options,
+};
+
+/// A group of N unsolved cells in one house with only N + 1 possible values between them. If any
+/// one of the values is ruled out, the rest are locked into the cells
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlmostLockedSet {
+    /// The cells in the set, sorted
+    pub cells: Vec<(usize, usize)>,
+    /// The possible values of each cell, in the same order as `cells`
+    pub possible: Vec<Candidates>,
+    /// Every value possible in the set
+    pub values: Candidates,
+}
+
+impl AlmostLockedSet {
+    /// Returns the cells in the set that have `value` as a possible value
+    pub fn cells_with(&self, value: u8) -> Vec<(usize, usize)> {
+        self.cells
+            .iter()
+            .zip(&self.possible)
+            .filter(|(_pos, possible)| possible.contains(value))
+            .map(|(pos, _possible)| *pos)
+            .collect()
+    }
+
+    /// Returns true if the two sets share any cells
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.cells.iter().any(|pos| other.cells.contains(pos))
+    }
+
+    /// Returns the values that are in both sets, where every cell with the value in one set sees
+    /// every cell with the value in the other. The value can be in at most one of the two sets,
+    /// so these are called restricted common candidates
+    pub fn restricted_commons(&self, other: &Self) -> Vec<u8> {
+        if self.overlaps(other) {
+            return Vec::new();
+        }
+        (self.values & other.values)
+            .iter()
+            .filter(|val| {
+                let theirs = other.cells_with(*val);
+                self.cells_with(*val)
+                    .iter()
+                    .all(|a| theirs.iter().all(|b| Board::sees(*a, *b)))
+            })
+            .collect()
+    }
+}
+
+/// Returns every almost locked set in the rows, cols, and groups of the board. A set that lies in
+/// more than one house is only returned once
+pub fn almost_locked_sets(board: &Board) -> Vec<AlmostLockedSet> {
+    let mut sets: Vec<AlmostLockedSet> = Vec::new();
+    for house in House::all().map(|house| board.house(house)) {
+        let unsolved: Vec<((usize, usize), Candidates)> = house
+            .iter()
+            .filter_map(|(pos, cell)| match cell {
+                Cell::Known(_) => None,
+                Cell::Possible(values) => Some((*pos, *values)),
+            })
+            .collect();
+        let indexes: Vec<usize> = (0..unsolved.len()).collect();
+
+        for size in 1..unsolved.len() {
+            for subset in make_combinations_from_valid_options(&indexes, size) {
+                let values = subset.iter().fold(Candidates::EMPTY, |values, index| {
+                    values | unsolved[*index].1
+                });
+                if values.len() != size + 1 {
+                    continue;
+                }
+                let set = AlmostLockedSet {
+                    cells: subset.iter().map(|index| unsolved[*index].0).collect(),
+                    possible: subset.iter().map(|index| unsolved[*index].1).collect(),
+                    values,
+                };
+                if !sets.contains(&set) {
+                    sets.push(set);
+                }
+            }
+        }
+    }
+
+    sets
+}
+
+/// Returns the name of a set, like "r1c2, r1c5 (1, 4, 7)"
+fn set_name(set: &AlmostLockedSet) -> String {
+    format!(
+        "{} ({})",
+        cell_names(&set.cells),
+        value_names(&set.values.to_vec())
+    )
+}
+
+/// Returns a step for a pattern made of almost locked sets, listing all of their cells and values
+fn sets_step(description: String, sets: &[&AlmostLockedSet]) -> Step {
+    let mut cells: Vec<(usize, usize)> = sets.iter().flat_map(|set| set.cells.clone()).collect();
+    cells.sort();
+    cells.dedup();
+    let values = sets
+        .iter()
+        .fold(Candidates::EMPTY, |values, set| values | set.values);
+    Step::new(description, cells, values.to_vec())
+}
+
+/// Returns the cells of every set that have `value` as a possible value
+fn cells_with(sets: &[&AlmostLockedSet], value: u8) -> Vec<(usize, usize)> {
+    sets.iter().flat_map(|set| set.cells_with(value)).collect()
+}
+
+/// If two almost locked sets share a restricted common candidate x, then x is in at most one of
+/// them, so the other is locked. Any other value z in both sets must then be in one of them, so
+/// z is removed from every cell that sees all of the z cells in both sets. If the sets share two
+/// restricted common candidates, both sets are locked, so every value is removed from the cells
+/// that see all of its cells in either set
+/// This strategy is called ALS-XZ, and the second case is called doubly linked ALS-XZ
+pub fn handle_als_xz(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+    let sets = almost_locked_sets(board);
+
+    for (index, a) in sets.iter().enumerate() {
+        for b in &sets[index + 1..] {
+            let restricted = a.restricted_commons(b);
+            if restricted.is_empty() {
+                continue;
+            }
+
+            let mut step = sets_step(
+                format!(
+                    "{} and {} can't both contain {}",
+                    set_name(a),
+                    set_name(b),
+                    value_names(&restricted)
+                ),
+                &[a, b],
+            );
+            for z in a.values & b.values {
+                if restricted.len() == 1 && restricted.contains(&z) {
+                    continue;
+                }
+                step.eliminate_from_common_peers(board, &cells_with(&[a, b], z), z);
+            }
+
+            if restricted.len() >= 2 {
+                for set in [a, b] {
+                    for value in set.values.iter().filter(|val| !restricted.contains(val)) {
+                        step.eliminate_from_common_peers(board, &set.cells_with(value), value);
+                    }
+                }
+            }
+            if step.record(log) {
+                updated = true;
+            }
+        }
+    }
+
+    updated
+}
+
+/// If almost locked sets A and B each share a different restricted common candidate with a pivot
+/// set C (x with A and y with B), then if A isn't locked C is, which locks B. So one of A or B is
+/// locked, and any value z in both of them is removed from every cell that sees all of their z
+/// cells
+/// This strategy is called ALS-XY-Wing
+pub fn handle_als_xy_wing(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+    let sets = almost_locked_sets(board);
+
+    for pivot in &sets {
+        let linked: Vec<(&AlmostLockedSet, Vec<u8>)> = sets
+            .iter()
+            .map(|set| (set, pivot.restricted_commons(set)))
+            .filter(|(_set, restricted)| !restricted.is_empty())
+            .collect();
+
+        for (index, (a, a_restricted)) in linked.iter().enumerate() {
+            for (b, b_restricted) in &linked[index + 1..] {
+                if a.overlaps(b) {
+                    continue;
+                }
+                // The two links need different values, and z can't be either of them
+                for x in a_restricted {
+                    for y in b_restricted.iter().filter(|y| *y != x) {
+                        let mut step = sets_step(
+                            format!(
+                                "{} is linked to {} by {x} and to {} by {y}",
+                                set_name(pivot),
+                                set_name(a),
+                                set_name(b)
+                            ),
+                            &[pivot, a, b],
+                        );
+                        for z in a.values & b.values {
+                            if z == *x || z == *y {
+                                continue;
+                            }
+                            step.eliminate_from_common_peers(board, &cells_with(&[a, b], z), z);
+                        }
+                        if step.record(log) {
+                            updated = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    updated
+}
+
+/// If every possible value of a stem cell has an almost locked set (a petal) where all the cells
+/// with that value see the stem, then whichever value the stem is locks that petal. Any value z
+/// that isn't possible in the stem but is in every petal is removed from every cell that sees all
+/// of the petals' z cells
+/// This strategy is called Death Blossom
+pub fn handle_death_blossom(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+    let sets = almost_locked_sets(board);
+
+    for row in 0..9 {
+        for col in 0..9 {
+            let stem_values = match board.get(row, col) {
+                Cell::Possible(values) if (2..=3).contains(&values.len()) => *values,
+                _ => continue,
+            };
+
+            // The sets that can be a petal for each value of the stem
+            let petals: Vec<Vec<&AlmostLockedSet>> = stem_values
+                .iter()
+                .map(|value| {
+                    sets.iter()
+                        .filter(|set| {
+                            !set.cells.contains(&(row, col))
+                                && set.values.contains(value)
+                                && set
+                                    .cells_with(value)
+                                    .iter()
+                                    .all(|pos| Board::sees(*pos, (row, col)))
+                        })
+                        .collect()
+                })
+                .collect();
+            if petals.iter().any(|petal| petal.is_empty()) {
+                continue;
+            }
+
+            let mut blossom: Vec<&AlmostLockedSet> = Vec::new();
+            if blossoms(board, (row, col), &petals, stem_values, &mut blossom, log) {
+                updated = true;
+            }
+        }
+    }
+
+    updated
+}
+
+/// Picks one non-overlapping petal for each stem value in turn, applying the death blossom
+/// eliminations for every complete set of petals. Returns true if any cell was updated
+fn blossoms<'a>(
+    board: &mut Board,
+    stem: (usize, usize),
+    petals: &[Vec<&'a AlmostLockedSet>],
+    stem_values: Candidates,
+    blossom: &mut Vec<&'a AlmostLockedSet>,
+    log: &mut Vec<Step>,
+) -> bool {
+    let Some((choices, rest)) = petals.split_first() else {
+        let petal_names: Vec<String> = blossom
+            .iter()
+            .zip(stem_values)
+            .map(|(set, value)| format!("{value} in {}", set_name(set)))
+            .collect();
+        let mut step = sets_step(
+            format!(
+                "stem {} has petals {}",
+                cell_name(stem),
+                petal_names.join(", ")
+            ),
+            blossom,
+        );
+        step.cells.insert(0, stem);
+        for z in 1..=9 {
+            if stem_values.contains(z) || !blossom.iter().all(|set| set.values.contains(z)) {
+                continue;
+            }
+            step.eliminate_from_common_peers(board, &cells_with(blossom, z), z);
+        }
+        return step.record(log);
+    };
+
+    let mut updated = false;
+    for petal in choices {
+        if blossom.iter().any(|set| set.overlaps(petal)) {
+            continue;
+        }
+        blossom.push(petal);
+        if blossoms(board, stem, rest, stem_values, blossom, log) {
+            updated = true;
+        }
+        blossom.pop();
+    }
+    updated
+}
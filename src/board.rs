@@ -1,14 +1,21 @@
-use std::collections::HashSet;
+use std::{fmt, str::FromStr};
 
-#[derive(Debug, Clone)]
+use crate::{
+    candidates::{Candidate, Candidates},
+    canonical::canonical_form,
+    dlx::count_dlx_solutions,
+    house::{self, peers_of, House},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Cell {
     Known(u8),
-    Possible(Vec<u8>),
+    Possible(Candidates),
 }
 
 impl Default for Cell {
     fn default() -> Self {
-        Self::Possible((1..=9).collect())
+        Self::Possible(Candidates::ALL)
     }
 }
 
@@ -18,8 +25,8 @@ impl Cell {
         match self {
             Cell::Known(_) => {}
             Cell::Possible(values) => {
-                if values.len() == 1 {
-                    *self = Self::Known(values[0]);
+                if let Some(value) = values.single_value() {
+                    *self = Self::Known(value);
                 }
             }
         }
@@ -41,269 +48,394 @@ impl Cell {
             Cell::Possible(_) => None,
         }
     }
+
+    /// Returns the number of possible values of a `Cell::Possible(_)`, or 0 for a `Cell::Known(_)`
+    pub fn possible_count(&self) -> usize {
+        match self {
+            Cell::Known(_) => 0,
+            Cell::Possible(values) => values.len(),
+        }
+    }
+
+    /// Returns true if `value` is one of the possible values of a `Cell::Possible(_)`
+    pub fn is_possible(&self, value: u8) -> bool {
+        match self {
+            Cell::Known(_) => false,
+            Cell::Possible(values) => values.contains(value),
+        }
+    }
+
+    /// Removes `value` from the possible values of a `Cell::Possible(_)` and checks if that
+    /// solves the cell. Returns true if `value` was removed
+    pub fn remove_possible(&mut self, value: u8) -> bool {
+        match self {
+            Cell::Known(_) => false,
+            Cell::Possible(values) => {
+                if values.remove(value) {
+                    self.check();
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Returns the possible values of a `Cell::Possible(_)`, or no values for a `Cell::Known(_)`
+    pub fn candidates(&self) -> Candidates {
+        match self {
+            Cell::Known(_) => Candidates::EMPTY,
+            Cell::Possible(values) => *values,
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+/// The 81 cells of the board, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Board {
-    board: Vec<Vec<Cell>>,
+    cells: [Cell; 81],
 }
 
 impl Default for Board {
     fn default() -> Self {
         Self {
-            board: vec![vec![Cell::default(); 9]; 9],
+            cells: [Cell::default(); 81],
         }
     }
 }
 
+/// Returns the index into the cells of the requested position
+fn index(row: usize, col: usize) -> usize {
+    row * 9 + col
+}
+
+/// Returns the position of the cell at `index`
+fn position(index: usize) -> (usize, usize) {
+    (index / 9, index % 9)
+}
+
 impl Board {
-    fn rows(&self) -> Vec<Vec<&Cell>> {
-        (0..9).map(|row| self.row(row)).collect()
+    /// Returns the cells of the house with their positions, in the order of `House::cells`
+    pub fn house(&self, house: House) -> [((usize, usize), &Cell); 9] {
+        house
+            .cells()
+            .map(|(row, col)| ((row, col), self.get(row, col)))
     }
 
-    fn cols(&self) -> Vec<Vec<&Cell>> {
-        (0..9).map(|col| self.col(col)).collect()
+    /// Returns the cells of the house with their positions, mutably, in the order of
+    /// `House::cells`
+    pub fn house_mut(&mut self, house: House) -> [((usize, usize), &mut Cell); 9] {
+        let positions = house.cells();
+        let mut cells = self
+            .cells
+            .get_disjoint_mut(positions.map(|(row, col)| index(row, col)))
+            .expect("the cells of a house are distinct")
+            .into_iter();
+        positions.map(|pos| (pos, cells.next().unwrap()))
     }
 
-    fn groups(&self) -> Vec<Vec<&Cell>> {
-        (0..3)
-            .flat_map(move |row| (0..3).map(move |col| self.group(row, col)))
-            .collect()
+    /// Returns a [&Cell; 9] referencing all the Cells in the requested col
+    pub fn col(&self, index: usize) -> [&Cell; 9] {
+        self.enum_col(index).map(|(_pos, cell)| cell)
     }
-}
 
-impl Board {
-    /// Returns a Vec<&Cell> referencing all the Cells in the requested col
-    pub fn col(&self, index: usize) -> Vec<&Cell> {
-        self.board
-            .iter()
-            .map(|row| row.get(index).unwrap())
-            .collect()
+    /// Returns a [&mut Cell; 9] mutably referencing all the Cells in the requested col
+    pub fn col_mut(&mut self, index: usize) -> [&mut Cell; 9] {
+        self.enum_col_mut(index).map(|(_pos, cell)| cell)
     }
 
-    /// Returns a Vec<&mut Cell> mutably referencing all the Cells in the requested col
-    pub fn col_mut(&mut self, index: usize) -> Vec<&mut Cell> {
-        self.board
-            .iter_mut()
-            .map(|row| row.get_mut(index).unwrap())
-            .collect()
+    pub fn enum_col(&self, col_index: usize) -> [((usize, usize), &Cell); 9] {
+        self.house(House::Col(col_index))
     }
 
-    pub fn enum_col(&self, col_index: usize) -> Vec<((usize, usize), &Cell)> {
-        self.board
-            .iter()
-            .enumerate()
-            .map(|(row_index, row)| ((row_index, col_index), row.get(col_index).unwrap()))
-            .collect()
+    pub fn enum_col_mut(&mut self, col_index: usize) -> [((usize, usize), &mut Cell); 9] {
+        self.house_mut(House::Col(col_index))
     }
 
-    pub fn enum_col_mut(&mut self, col_index: usize) -> Vec<((usize, usize), &mut Cell)> {
-        self.board
-            .iter_mut()
-            .enumerate()
-            .map(|(row_index, row)| ((row_index, col_index), row.get_mut(col_index).unwrap()))
-            .collect()
+    /// Returns a [&Cell; 9] referencing all the Cells in the requested row
+    pub fn row(&self, index: usize) -> [&Cell; 9] {
+        self.enum_row(index).map(|(_pos, cell)| cell)
     }
 
-    /// Returns a Vec<&Cell> referencing all the Cells in the requested row
-    pub fn row(&self, index: usize) -> Vec<&Cell> {
-        self.board.get(index).unwrap().iter().collect()
+    /// Returns a [&mut Cell; 9] mutably referencing all the Cells in the requested row
+    pub fn row_mut(&mut self, index: usize) -> [&mut Cell; 9] {
+        self.enum_row_mut(index).map(|(_pos, cell)| cell)
     }
 
-    /// Returns a Vec<&mut Cell> mutably referencing all the Cells in the requested row
-    pub fn row_mut(&mut self, index: usize) -> Vec<&mut Cell> {
-        self.board.get_mut(index).unwrap().iter_mut().collect()
+    pub fn enum_row(&self, row_index: usize) -> [((usize, usize), &Cell); 9] {
+        self.house(House::Row(row_index))
     }
 
-    pub fn enum_row(&self, row_index: usize) -> Vec<((usize, usize), &Cell)> {
-        self.board
-            .get(row_index)
-            .unwrap()
+    pub fn enum_row_mut(&mut self, row_index: usize) -> [((usize, usize), &mut Cell); 9] {
+        self.house_mut(House::Row(row_index))
+    }
+
+    /// Returns true if the two positions are different cells that share a row, col, or group
+    pub fn sees(a: (usize, usize), b: (usize, usize)) -> bool {
+        house::sees(a, b)
+    }
+
+    /// Returns the positions of the 20 cells that share a row, col, or group with the requested
+    /// position
+    pub fn peers(row: usize, col: usize) -> &'static [(usize, usize); 20] {
+        peers_of((row, col))
+    }
+
+    /// Returns the positions and possible values of every unsolved cell with exactly `count`
+    /// possible values
+    pub fn cells_with_possible_count(&self, count: usize) -> Vec<((usize, usize), Candidates)> {
+        self.cells
             .iter()
             .enumerate()
-            .map(|(col_index, cell)| ((row_index, col_index), cell))
+            .filter_map(|(index, cell)| match cell {
+                Cell::Possible(values) if values.len() == count => Some((position(index), *values)),
+                _ => None,
+            })
             .collect()
     }
 
-    pub fn enum_row_mut(&mut self, row_index: usize) -> Vec<((usize, usize), &mut Cell)> {
-        self.board
-            .get_mut(row_index)
-            .unwrap()
-            .iter_mut()
+    /// Returns every possible value of every unsolved cell, with the position of its cell
+    pub fn candidates(&self) -> Vec<Candidate> {
+        self.cells
+            .iter()
             .enumerate()
-            .map(|(col_index, cell)| ((row_index, col_index), cell))
+            .flat_map(|(index, cell)| {
+                cell.candidates()
+                    .iter()
+                    .map(move |val| (position(index), val))
+            })
             .collect()
     }
 
+    /// Returns the positions of the cells in each of the 9 rows, 9 cols, and 9 groups
+    pub fn houses() -> impl Iterator<Item = &'static [(usize, usize); 9]> {
+        House::all().map(House::cells)
+    }
+
+    /// Returns every pair of cells that are the only two possible positions for `value` in a row,
+    /// col, or group. Either one or the other of the pair must be `value`
+    pub fn conjugate_pairs(&self, value: u8) -> Vec<((usize, usize), (usize, usize))> {
+        let mut pairs = Vec::new();
+        for house in Self::houses() {
+            if house
+                .iter()
+                .any(|(row, col)| self.get(*row, *col).value() == Some(value))
+            {
+                continue;
+            }
+            let found: Vec<(usize, usize)> = house
+                .iter()
+                .copied()
+                .filter(|(row, col)| self.get(*row, *col).is_possible(value))
+                .collect();
+            if found.len() == 2 && !pairs.contains(&(found[0], found[1])) {
+                pairs.push((found[0], found[1]));
+            }
+        }
+        pairs
+    }
+
     /// Returns a &Cell from the requested position
     pub fn get(&self, row: usize, col: usize) -> &Cell {
-        &self.board[row][col]
+        &self.cells[index(row, col)]
     }
 
     /// Returns a &mut Cell from the requested position
     pub fn get_mut(&mut self, row: usize, col: usize) -> &mut Cell {
-        &mut self.board[row][col]
+        &mut self.cells[index(row, col)]
     }
 
-    /// Returns a Vec<&Cell> referencing all the Cells in the requested group
-    pub fn group(&self, row: usize, col: usize) -> Vec<&Cell> {
-        self.board
-            .iter()
-            .enumerate()
-            .filter_map(|(row_i, vals)| {
-                if row_i >= row * 3 && row_i < row * 3 + 3 {
-                    Some(vals)
-                } else {
-                    None
-                }
-            })
-            .flat_map(|row| row.iter().enumerate())
-            .filter_map(|(col_i, vals)| {
-                if col_i >= col * 3 && col_i < col * 3 + 3 {
-                    Some(vals)
-                } else {
-                    None
-                }
-            })
-            .collect()
+    /// Returns a [&Cell; 9] referencing all the Cells in the requested group
+    pub fn group(&self, row: usize, col: usize) -> [&Cell; 9] {
+        self.enum_group(row, col).map(|(_pos, cell)| cell)
     }
 
-    /// Returns a Vec<&mut Cell> mutably referencing all the Cells in the requested group
-    pub fn group_mut(&mut self, row: usize, col: usize) -> Vec<&mut Cell> {
-        self.board
-            .iter_mut()
-            .enumerate()
-            .filter_map(|(row_i, vals)| {
-                if row_i >= row * 3 && row_i < row * 3 + 3 {
-                    Some(vals)
-                } else {
-                    None
-                }
-            })
-            .flat_map(|row| row.iter_mut().enumerate())
-            .filter_map(|(col_i, vals)| {
-                if col_i >= col * 3 && col_i < col * 3 + 3 {
-                    Some(vals)
-                } else {
-                    None
-                }
-            })
-            .collect()
+    /// Returns a [&mut Cell; 9] mutably referencing all the Cells in the requested group
+    pub fn group_mut(&mut self, row: usize, col: usize) -> [&mut Cell; 9] {
+        self.enum_group_mut(row, col).map(|(_pos, cell)| cell)
     }
 
-    pub fn enum_group(&self, row: usize, col: usize) -> Vec<((usize, usize), &Cell)> {
-        self.board
-            .iter()
-            .enumerate()
-            .filter_map(|(row_i, vals)| {
-                if row_i >= row * 3 && row_i < row * 3 + 3 {
-                    Some((row_i, vals))
-                } else {
-                    None
-                }
-            })
-            .flat_map(|(row_i, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(move |(col_i, cell)| ((row_i, col_i), cell))
-            })
-            .filter_map(|((row_i, col_i), cell)| {
-                if col_i >= col * 3 && col_i < col * 3 + 3 {
-                    Some(((row_i, col_i), cell))
-                } else {
-                    None
-                }
-            })
-            .collect()
+    pub fn enum_group(&self, row: usize, col: usize) -> [((usize, usize), &Cell); 9] {
+        self.house(House::Group(row * 3 + col))
     }
 
-    pub fn enum_group_mut(&mut self, row: usize, col: usize) -> Vec<((usize, usize), &mut Cell)> {
-        self.board
-            .iter_mut()
-            .enumerate()
-            .filter_map(|(row_i, vals)| {
-                if row_i >= row * 3 && row_i < row * 3 + 3 {
-                    Some((row_i, vals))
-                } else {
-                    None
-                }
-            })
-            .flat_map(|(row_i, row)| {
-                row.iter_mut()
-                    .enumerate()
-                    .map(move |(col_i, cell)| ((row_i, col_i), cell))
-            })
-            .filter_map(|((row_i, col_i), cell)| {
-                if col_i >= col * 3 && col_i < col * 3 + 3 {
-                    Some(((row_i, col_i), cell))
-                } else {
-                    None
-                }
-            })
-            .collect()
+    pub fn enum_group_mut(&mut self, row: usize, col: usize) -> [((usize, usize), &mut Cell); 9] {
+        self.house_mut(House::Group(row * 3 + col))
     }
 
     /// Returns the number of cells that are not Cell::Known
     pub fn num_unsolved(&self) -> usize {
-        self.board.iter().flatten().filter(|cell| !cell.is_known()).count()
+        self.cells.iter().filter(|cell| !cell.is_known()).count()
     }
 
     pub fn num_possible_values(&self) -> usize {
-        self.board.iter().flatten().map(|cell| {
-            match cell {
-                Cell::Known(_) => 0,
-                Cell::Possible(values) => values.len(),
-            }
-        }).sum()
+        self.cells.iter().map(|cell| cell.possible_count()).sum()
     }
 
     pub fn contains_bad_cells(&self) -> bool {
-        self.board.iter().flatten().map(|cell| {
+        self.cells.iter().any(|cell| {
             match cell {
                 Cell::Known(_) => false,
                 Cell::Possible(values) => values.is_empty(),
             }
-        }).any(|val| val)
+        })
+    }
+
+    /// Returns the position of the unsolved cell with the fewest possible values, or None if
+    /// every cell is Cell::Known
+    pub fn most_constrained(&self) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| match cell {
+                Cell::Known(_) => None,
+                Cell::Possible(values) => Some((position(index), values.len())),
+            })
+            .min_by_key(|(_pos, len)| *len)
+            .map(|(pos, _len)| pos)
+    }
+
+    /// Returns the number of ways the board can be filled in, counting no further than `limit`.
+    /// Only the known values and possible values of the board are used, so any possible value
+    /// that has already been removed stays removed
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        count_dlx_solutions(self, limit)
+    }
+
+    /// Returns true if the board can be filled in exactly one way. Stops as soon as a second
+    /// solution is found
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    /// Returns the canonical form of the puzzle, which is the same for every puzzle that only
+    /// differs from it by relabeling values or by rearranging rows, cols, bands, and stacks
+    pub fn canonical_form(&self) -> String {
+        canonical_form(self)
     }
 
     /// Returns true if all rows, cols, and groups contain the values 1..=9
     pub fn is_correct(&self) -> bool {
-        self.rows().iter().all(|row| {
-            let mut check = HashSet::new();
-            row.iter()
-                .filter_map(|cell| cell.value())
-                .all(|val| val <= 9 && check.insert(val))
-        }) && self.cols().iter().all(|col| {
-            let mut check = HashSet::new();
-            col.iter()
-                .filter_map(|cell| cell.value())
-                .all(|val| val <= 9 && check.insert(val))
-        }) && self.groups().iter().all(|group| {
-            let mut check = HashSet::new();
-            group
+        House::all().all(|house| {
+            let mut seen: u16 = 0;
+            self.house(house)
                 .iter()
-                .filter_map(|cell| cell.value())
-                .all(|val| val <= 9 && check.insert(val))
+                .filter_map(|(_pos, cell)| cell.value())
+                .all(|val| {
+                    if val > 9 || seen & (1 << val) != 0 {
+                        return false;
+                    }
+                    seen |= 1 << val;
+                    true
+                })
         })
     }
+}
 
-    pub fn to_string(&self) -> String {
-        let mut out = String::new();
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for row in 0..9 {
-            self.row(row).iter().for_each(|cell| {
-                out.push_str(&match cell {
-                    Cell::Known(value) => value.to_string(),
+            for cell in self.row(row) {
+                match cell {
+                    Cell::Known(value) => write!(f, "{value}")?,
                     Cell::Possible(values) => {
                         if values.is_empty() {
-                            String::from("X")
+                            write!(f, "X")?;
                         } else {
-                            String::from("?")
+                            write!(f, "?")?;
                         }
-                    },
-                });
-            });
-            out.push('\n');
+                    }
+                }
+            }
+            writeln!(f)?;
         }
-        out
+        Ok(())
+    }
+}
+
+/// Why a puzzle string couldn't be turned into a board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBoardError {
+    /// The puzzle didn't have exactly 81 cells. Holds the number it had
+    WrongLength(usize),
+    /// A cell wasn't a digit from 1 to 9, or 0 or . for an empty cell
+    BadCharacter(char),
+}
+
+impl fmt::Display for ParseBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBoardError::WrongLength(len) => write!(f, "expected 81 cells, found {len}"),
+            ParseBoardError::BadCharacter(char) => write!(f, "unexpected character: {char}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseBoardError {}
+
+impl FromStr for Board {
+    type Err = ParseBoardError;
+
+    /// Reads the 81 cells row by row, with 1 to 9 for a given and 0 or . for an empty cell.
+    /// Whitespace is skipped, so the puzzle can be on one line or split over several
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let cells: Vec<char> = input.chars().filter(|char| !char.is_whitespace()).collect();
+        if cells.len() != 81 {
+            return Err(ParseBoardError::WrongLength(cells.len()));
+        }
+
+        let mut board = Board::default();
+        for (index, char) in cells.into_iter().enumerate() {
+            match char {
+                '0' | '.' => {}
+                '1'..='9' => board.cells[index] = Cell::Known(char as u8 - b'0'),
+                _ => return Err(ParseBoardError::BadCharacter(char)),
+            }
+        }
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A puzzle with exactly one solution, `SOLUTION`
+    pub(crate) const PUZZLE: &str =
+        "000000300001007006540000008000708020060009050008000040000000080150300000200010007";
+    pub(crate) const SOLUTION: &str =
+        "672894315831527496549163278415738629763249851928651743396475182157382964284916537";
+
+    #[test]
+    fn parses_a_puzzle_on_one_line_or_several() {
+        let board: Board = PUZZLE.parse().unwrap();
+        assert_eq!(board.get(0, 6), &Cell::Known(3));
+        assert_eq!(board.get(0, 0), &Cell::default());
+        assert_eq!(board.num_unsolved(), 59);
+
+        let lines: Vec<&str> = (0..9).map(|row| &PUZZLE[row * 9..row * 9 + 9]).collect();
+        assert_eq!(lines.join("\n").parse(), Ok(board.clone()));
+        assert_eq!(PUZZLE.replace('0', ".").parse(), Ok(board));
+    }
+
+    #[test]
+    fn rejects_a_puzzle_of_the_wrong_length() {
+        assert_eq!(
+            PUZZLE[1..].parse::<Board>(),
+            Err(ParseBoardError::WrongLength(80))
+        );
+        assert_eq!(
+            format!("{PUZZLE}1").parse::<Board>(),
+            Err(ParseBoardError::WrongLength(82))
+        );
+    }
+
+    #[test]
+    fn rejects_a_bad_character() {
+        let puzzle = format!("x{}", &PUZZLE[1..]);
+        assert_eq!(
+            puzzle.parse::<Board>(),
+            Err(ParseBoardError::BadCharacter('x'))
+        );
     }
 }
@@ -0,0 +1,257 @@
+use std::{
+    fmt,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Sub},
+};
+
+/// A possible `value` at a `(row, col)` position
+pub type Candidate = ((usize, usize), u8);
+
+/// A set of the values 1 to 9 stored as bits of a `u16`, where bit `value` is set if `value` is
+/// in the set. Used for the possible values of an unsolved cell
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Candidates(u16);
+
+impl Candidates {
+    /// Every value from 1 to 9
+    pub const ALL: Self = Self(0b11_1111_1110);
+    /// No values
+    pub const EMPTY: Self = Self(0);
+
+    /// Returns the set containing only `value`, or no values if it is outside 1 to 9
+    pub fn single(value: u8) -> Self {
+        let mut set = Self::EMPTY;
+        set.insert(value);
+        set
+    }
+
+    /// Returns the set of values whose bits are set in `bits`. Bits outside 1 to 9 are ignored
+    pub fn from_bits(bits: u16) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+
+    /// Returns the raw bits of the set
+    pub fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the number of values in the set
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns true if there are no values in the set
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if `value` is in the set
+    pub fn contains(self, value: u8) -> bool {
+        (1..=9).contains(&value) && self.0 & (1 << value) != 0
+    }
+
+    /// Adds `value` to the set. Returns true if it wasn't already there. Values outside 1 to 9
+    /// are ignored
+    pub fn insert(&mut self, value: u8) -> bool {
+        if !(1..=9).contains(&value) || self.contains(value) {
+            return false;
+        }
+        self.0 |= 1 << value;
+        true
+    }
+
+    /// Removes `value` from the set. Returns true if it was there
+    pub fn remove(&mut self, value: u8) -> bool {
+        if !self.contains(value) {
+            return false;
+        }
+        self.0 &= !(1 << value);
+        true
+    }
+
+    /// Returns the smallest value in the set, or None if it is empty
+    pub fn first(self) -> Option<u8> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.0.trailing_zeros() as u8)
+        }
+    }
+
+    /// Returns the value if the set contains exactly one, or None otherwise
+    pub fn single_value(self) -> Option<u8> {
+        if self.len() == 1 {
+            self.first()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the two values, smallest first, if the set contains exactly two, or None otherwise
+    pub fn pair(self) -> Option<(u8, u8)> {
+        let mut values = self.iter();
+        match (values.next(), values.next(), values.next()) {
+            (Some(first), Some(second), None) => Some((first, second)),
+            _ => None,
+        }
+    }
+
+    /// Returns true if every value in the set is also in `other`
+    pub fn is_subset(self, other: Self) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// Returns the values in increasing order
+    pub fn iter(self) -> Iter {
+        Iter(self.0)
+    }
+
+    /// Returns the values in increasing order as a Vec
+    pub fn to_vec(self) -> Vec<u8> {
+        self.iter().collect()
+    }
+}
+
+/// The values of a `Candidates`, smallest first
+#[derive(Clone)]
+pub struct Iter(u16);
+
+impl Iterator for Iter {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.0 == 0 {
+            return None;
+        }
+        let value = self.0.trailing_zeros() as u8;
+        // Clear the lowest set bit
+        self.0 &= self.0 - 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+impl IntoIterator for Candidates {
+    type Item = u8;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &Candidates {
+    type Item = u8;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl FromIterator<u8> for Candidates {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut set = Self::EMPTY;
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl<'a> FromIterator<&'a u8> for Candidates {
+    fn from_iter<T: IntoIterator<Item = &'a u8>>(iter: T) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl BitAnd for Candidates {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+impl BitAndAssign for Candidates {
+    fn bitand_assign(&mut self, other: Self) {
+        self.0 &= other.0;
+    }
+}
+
+impl BitOr for Candidates {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Candidates {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl Sub for Candidates {
+    type Output = Self;
+
+    /// Returns the values in `self` that aren't in `other`
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+}
+
+impl Not for Candidates {
+    type Output = Self;
+
+    /// Returns the values from 1 to 9 that aren't in the set
+    fn not(self) -> Self {
+        Self(!self.0 & Self::ALL.0)
+    }
+}
+
+impl fmt::Debug for Candidates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_values_outside_one_to_nine() {
+        let mut set = Candidates::EMPTY;
+        assert!(!set.insert(0));
+        assert!(!set.insert(10));
+        assert!(!set.insert(16));
+        assert!(set.is_empty());
+        assert_eq!(Candidates::single(0), Candidates::EMPTY);
+        assert_eq!(Candidates::single(16), Candidates::EMPTY);
+
+        let mut set = Candidates::ALL;
+        assert!(!set.remove(0));
+        assert!(!set.remove(200));
+        assert_eq!(set, Candidates::ALL);
+    }
+
+    #[test]
+    fn every_method_agrees_on_the_values() {
+        let mut set = Candidates::single(4);
+        assert!(set.insert(9));
+        assert!(!set.insert(9));
+        assert!(set.insert(1));
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.to_vec(), vec![1, 4, 9]);
+        assert!((0..=15).all(|value| set.contains(value) == [1, 4, 9].contains(&value)));
+        assert!(set.remove(4));
+        assert_eq!(set.pair(), Some((1, 9)));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::board::Board;
+
+/// Every order of three things
+const PERMUTATIONS: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+/// Returns every order the 9 rows (or cols) can be put in without breaking up the bands (or
+/// stacks): the three bands in any order, and the rows within each band in any order
+fn line_orders() -> Vec<[usize; 9]> {
+    let mut orders = Vec::with_capacity(6 * 6 * 6 * 6);
+    for bands in PERMUTATIONS {
+        for first in PERMUTATIONS {
+            for second in PERMUTATIONS {
+                for third in PERMUTATIONS {
+                    let mut order = [0; 9];
+                    for (band, lines) in [first, second, third].iter().enumerate() {
+                        for (index, line) in lines.iter().enumerate() {
+                            order[band * 3 + index] = bands[band] * 3 + line;
+                        }
+                    }
+                    orders.push(order);
+                }
+            }
+        }
+    }
+    orders
+}
+
+/// The state of the search for the smallest form of a grid with its cols in a fixed order
+struct Search<'a> {
+    grid: &'a [u8; 81],
+    cols: &'a [usize; 9],
+    /// The smallest form found so far, from any order of the rows and cols
+    best: [u8; 81],
+    /// The form being built, filled in up to the current row
+    current: [u8; 81],
+}
+
+impl Search<'_> {
+    /// Picks the source row for each output row in turn, from `row` down. The rows of a band stay
+    /// together, so the first row of each band may come from any unused band and the rest must
+    /// come from `band`, the band that row came from. Values are relabeled in the order they
+    /// first appear, carried down in `labels`. A choice is dropped as soon as the form so far is
+    /// larger than the best one
+    fn rows(&mut self, row: usize, band: usize, used: [bool; 9], labels: [u8; 10], next_label: u8) {
+        if row == 9 {
+            if self.current < self.best {
+                self.best = self.current;
+            }
+            return;
+        }
+
+        for source in 0..9 {
+            let fits = if row % 3 == 0 {
+                !used[source / 3 * 3..source / 3 * 3 + 3].contains(&true)
+            } else {
+                source / 3 == band && !used[source]
+            };
+            if !fits {
+                continue;
+            }
+
+            let mut labels = labels;
+            let mut next_label = next_label;
+            for col in 0..9 {
+                let value = self.grid[source * 9 + self.cols[col]] as usize;
+                if value != 0 && labels[value] == 0 {
+                    labels[value] = next_label;
+                    next_label += 1;
+                }
+                self.current[row * 9 + col] = labels[value];
+            }
+            let end = row * 9 + 9;
+            if self.current[..end] > self.best[..end] {
+                continue;
+            }
+
+            let mut used = used;
+            used[source] = true;
+            self.rows(row + 1, source / 3, used, labels, next_label);
+        }
+    }
+}
+
+/// Returns the smallest 81 character string, read row by row with 0 for an empty cell, that the
+/// puzzle can be turned into by relabeling the values, reordering the rows within a band or the
+/// cols within a stack, reordering the bands or stacks, and transposing. Puzzles that are the
+/// same apart from those changes have the same canonical form. Only the known values are used
+pub fn canonical_form(board: &Board) -> String {
+    let mut grid = [0; 81];
+    let mut transposed = [0; 81];
+    for row in 0..9 {
+        for col in 0..9 {
+            let value = board.get(row, col).value().unwrap_or(0);
+            grid[row * 9 + col] = value;
+            transposed[col * 9 + row] = value;
+        }
+    }
+
+    let orders = line_orders();
+    let mut best = [u8::MAX; 81];
+    for grid in [&grid, &transposed] {
+        for cols in &orders {
+            let mut search = Search {
+                grid,
+                cols,
+                best,
+                current: [0; 81],
+            };
+            search.rows(0, 0, [false; 9], [0; 10], 1);
+            best = search.best;
+        }
+    }
+
+    best.iter().map(|value| char::from(b'0' + value)).collect()
+}
+
+/// Returns true if the puzzles are the same apart from relabeling the values, reordering rows
+/// and cols within their bands and stacks, reordering bands and stacks, and transposing
+pub fn are_equivalent(a: &Board, b: &Board) -> bool {
+    canonical_form(a) == canonical_form(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        board::{tests::PUZZLE, Cell},
+        generator::Rng,
+    };
+
+    /// Returns a random order of the 9 rows (or cols) that keeps the bands (or stacks) together
+    fn random_order(rng: &mut Rng) -> [usize; 9] {
+        let mut bands = [0, 1, 2];
+        rng.shuffle(&mut bands);
+        let mut order = [0; 9];
+        for (band, first) in bands.iter().enumerate() {
+            let mut lines = [0, 1, 2];
+            rng.shuffle(&mut lines);
+            for (index, line) in lines.iter().enumerate() {
+                order[band * 3 + index] = first * 3 + line;
+            }
+        }
+        order
+    }
+
+    /// Returns the board with its values relabeled, its rows, cols, bands, and stacks reordered,
+    /// and possibly transposed, all at random
+    fn random_transform(board: &Board, rng: &mut Rng) -> Board {
+        let rows = random_order(rng);
+        let cols = random_order(rng);
+        let transpose = rng.below(2) == 1;
+        let mut labels: Vec<u8> = (1..=9).collect();
+        rng.shuffle(&mut labels);
+
+        let mut transformed = Board::default();
+        for (row, row_source) in rows.iter().enumerate() {
+            for (col, col_source) in cols.iter().enumerate() {
+                let (source_row, source_col) = if transpose {
+                    (*col_source, *row_source)
+                } else {
+                    (*row_source, *col_source)
+                };
+                if let Some(value) = board.get(source_row, source_col).value() {
+                    *transformed.get_mut(row, col) = Cell::Known(labels[value as usize - 1]);
+                }
+            }
+        }
+        transformed
+    }
+
+    #[test]
+    fn random_transforms_keep_the_canonical_form() {
+        let puzzle = PUZZLE.parse::<Board>().unwrap();
+        let form = canonical_form(&puzzle);
+        let mut rng = Rng::new(1);
+        for _ in 0..200 {
+            let transformed = random_transform(&puzzle, &mut rng);
+            assert_eq!(canonical_form(&transformed), form);
+            assert!(are_equivalent(&puzzle, &transformed));
+        }
+    }
+
+    #[test]
+    fn different_puzzles_are_not_equivalent() {
+        let puzzle = PUZZLE.parse::<Board>().unwrap();
+        let mut changed = puzzle.clone();
+        *changed.get_mut(0, 0) = Cell::Known(9);
+        assert!(!are_equivalent(&puzzle, &changed));
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::VecDeque;
+
+use crate::{
+    board::{Board, Cell},
+    candidates::Candidate,
+    coloring::strong_links,
+    config::SolverConfig,
+    step::{cell_name, Step},
+};
+
+/// Which candidates and links a chain is allowed to use
+#[derive(Debug, Clone, Copy)]
+enum ChainKind {
+    /// A single value, linked through conjugate pairs
+    X(u8),
+    /// Only cells with two possible values, strongly linked inside each cell
+    Xy,
+    /// Any candidate, with any strong or weak link
+    Alternating,
+}
+
+/// Returns true if the two candidates can't both be true, because they are different values in
+/// the same cell, or the same value in cells that see each other
+fn weakly_linked(a: Candidate, b: Candidate) -> bool {
+    a != b && (a.0 == b.0 || (a.1 == b.1 && Board::sees(a.0, b.0)))
+}
+
+/// The candidates a chain can pass through, with the strong and weak links between them stored
+/// as indexes into `nodes`
+struct ChainGraph {
+    nodes: Vec<Candidate>,
+    strong: Vec<Vec<usize>>,
+    weak: Vec<Vec<usize>>,
+}
+
+impl ChainGraph {
+    fn new(board: &Board, kind: ChainKind) -> Self {
+        let (nodes, links): (Vec<Candidate>, _) = match kind {
+            ChainKind::X(value) => (
+                board
+                    .candidates()
+                    .into_iter()
+                    .filter(|(_pos, val)| *val == value)
+                    .collect(),
+                strong_links(board, &[value], false),
+            ),
+            ChainKind::Xy => (
+                board
+                    .candidates()
+                    .into_iter()
+                    .filter(|(pos, _val)| board.get(pos.0, pos.1).possible_count() == 2)
+                    .collect(),
+                strong_links(board, &[], true),
+            ),
+            ChainKind::Alternating => (
+                board.candidates(),
+                strong_links(board, &(1..=9).collect::<Vec<u8>>(), true),
+            ),
+        };
+
+        let index_of = |candidate: &Candidate| nodes.iter().position(|node| node == candidate);
+        let mut strong = vec![Vec::new(); nodes.len()];
+        for (a, b) in &links {
+            if let (Some(a), Some(b)) = (index_of(a), index_of(b)) {
+                strong[a].push(b);
+                strong[b].push(a);
+            }
+        }
+        let weak = nodes
+            .iter()
+            .map(|a| {
+                (0..nodes.len())
+                    .filter(|b| weakly_linked(*a, nodes[*b]))
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            nodes,
+            strong,
+            weak,
+        }
+    }
+
+    /// Follows alternating links from `start` assuming it is false, and returns the shortest
+    /// chain to every candidate that would then have to be true. Each chain is the list of node
+    /// indexes from `start` to the candidate, where even positions are false and odd positions
+    /// are true
+    fn search(&self, start: usize, max_length: usize) -> Vec<Vec<usize>> {
+        // States are `node * 2 + is_true`
+        let mut parent: Vec<Option<usize>> = vec![None; self.nodes.len() * 2];
+        let mut visited = vec![false; self.nodes.len() * 2];
+        let mut queue = VecDeque::from([(start * 2, 0)]);
+        visited[start * 2] = true;
+        let mut chains = Vec::new();
+
+        while let Some((state, length)) = queue.pop_front() {
+            if length >= max_length {
+                continue;
+            }
+            let (node, is_true) = (state / 2, state % 2 == 1);
+            // A false candidate makes its strong links true, a true one makes its weak links false
+            let next = if is_true {
+                &self.weak[node]
+            } else {
+                &self.strong[node]
+            };
+            for next in next {
+                let next_state = next * 2 + usize::from(!is_true);
+                if visited[next_state] {
+                    continue;
+                }
+                visited[next_state] = true;
+                parent[next_state] = Some(state);
+                queue.push_back((next_state, length + 1));
+
+                if !is_true {
+                    let mut chain = vec![*next];
+                    let mut current = state;
+                    chain.push(current / 2);
+                    while let Some(previous) = parent[current] {
+                        chain.push(previous / 2);
+                        current = previous;
+                    }
+                    chain.reverse();
+                    chains.push(chain);
+                }
+            }
+        }
+
+        chains
+    }
+}
+
+/// Returns a step for a chain, written as its candidates joined by `=` for strong links and `-`
+/// for weak links
+fn chain_step(graph: &ChainGraph, chain: &[usize], description: &str) -> Step {
+    let mut text = String::new();
+    for (index, node) in chain.iter().enumerate() {
+        if index > 0 {
+            text.push(if index % 2 == 1 { '=' } else { '-' });
+        }
+        let (pos, value) = graph.nodes[*node];
+        text.push_str(&format!("{value}{}", cell_name(pos)));
+    }
+    let mut cells: Vec<(usize, usize)> = chain.iter().map(|node| graph.nodes[*node].0).collect();
+    cells.sort();
+    cells.dedup();
+    let mut digits: Vec<u8> = chain.iter().map(|node| graph.nodes[*node].1).collect();
+    digits.sort();
+    digits.dedup();
+    Step::new(format!("{text} {description}"), cells, digits)
+}
+
+/// Returns every candidate that is weakly linked to `candidate`
+fn weak_targets(board: &Board, candidate: Candidate) -> Vec<Candidate> {
+    let (row, col) = candidate.0;
+    let mut cells = Board::peers(row, col).to_vec();
+    cells.push((row, col));
+    cells
+        .into_iter()
+        .flat_map(|(row, col)| match board.get(row, col) {
+            Cell::Known(_) => Vec::new(),
+            Cell::Possible(values) => values.iter().map(|val| ((row, col), val)).collect(),
+        })
+        .filter(|target| weakly_linked(*target, candidate))
+        .collect()
+}
+
+/// Removes every candidate that is weakly linked to both `a` and `b`, since one of them must be
+/// true, recording it in the step
+fn remove_weakly_linked(board: &mut Board, step: &mut Step, a: Candidate, b: Candidate) {
+    for (pos, value) in weak_targets(board, a) {
+        if (pos, value) != b && weakly_linked((pos, value), b) {
+            step.eliminate(board, pos, value);
+        }
+    }
+}
+
+/// Searches for chains of alternating strong and weak links of the given kind. If the start of a
+/// chain is false the end must be true, so one of them is true and any candidate that can't be
+/// true alongside either of them is removed. If the end also can't be true alongside the start
+/// the chain is a continuous loop, and the same applies to both ends of every weak link in it.
+/// When the chain starts and ends on the same candidate (a discontinuous nice loop) this removes
+/// every other candidate the start sees, solving it
+fn handle_chains(
+    board: &mut Board,
+    kind: ChainKind,
+    config: &SolverConfig,
+    log: &mut Vec<Step>,
+) -> bool {
+    let mut updated = false;
+    let graph = ChainGraph::new(board, kind);
+
+    for start in 0..graph.nodes.len() {
+        let chains = graph.search(start, config.max_chain_length);
+        let start_node = graph.nodes[start];
+
+        for target in weak_targets(board, start_node) {
+            let chain = chains.iter().find(|chain| {
+                let end = graph.nodes[*chain.last().unwrap()];
+                end != target && weakly_linked(target, end)
+            });
+            let Some(chain) = chain else {
+                continue;
+            };
+            let mut step = chain_step(&graph, chain, "has one end true");
+            step.eliminate(board, target.0, target.1);
+            if step.record(log) {
+                updated = true;
+            }
+        }
+
+        for chain in &chains {
+            // A continuous loop needs the end weakly linked back to the start, without the chain
+            // passing through the same candidate twice
+            let end_node = graph.nodes[*chain.last().unwrap()];
+            let distinct = chain
+                .iter()
+                .enumerate()
+                .all(|(index, node)| !chain[index + 1..].contains(node));
+            if !distinct || !weakly_linked(start_node, end_node) {
+                continue;
+            }
+            let mut step = chain_step(&graph, chain, "is a continuous loop");
+            // Every odd position is true and weakly linked to the false candidate after it
+            for index in (1..chain.len() - 1).step_by(2) {
+                let (a, b) = (graph.nodes[chain[index]], graph.nodes[chain[index + 1]]);
+                remove_weakly_linked(board, &mut step, a, b);
+            }
+            remove_weakly_linked(board, &mut step, end_node, start_node);
+            if step.record(log) {
+                updated = true;
+            }
+        }
+    }
+
+    updated
+}
+
+/// Chains of conjugate pairs for a single value, linked by cells that see each other
+/// This strategy is called X-Chain
+pub fn handle_x_chain(board: &mut Board, config: &SolverConfig, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+    for value in 1..=9 {
+        if handle_chains(board, ChainKind::X(value), config, log) {
+            updated = true;
+        }
+    }
+    updated
+}
+
+/// Chains of cells with two possible values, each linked to the next through a shared value
+/// This strategy is called XY-Chain
+pub fn handle_xy_chain(board: &mut Board, config: &SolverConfig, log: &mut Vec<Step>) -> bool {
+    handle_chains(board, ChainKind::Xy, config, log)
+}
+
+/// Chains mixing any values, conjugate pairs, and cells with two possible values, including
+/// nice loops
+/// This strategy is called Alternating Inference Chain (AIC)
+pub fn handle_aic(board: &mut Board, config: &SolverConfig, log: &mut Vec<Step>) -> bool {
+    handle_chains(board, ChainKind::Alternating, config, log)
+}
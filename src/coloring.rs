@@ -0,0 +1,242 @@
+use crate::{
+    board::{Board, Cell},
+    candidates::Candidate,
+    step::Step,
+};
+
+/// Returns every strong link between candidates: the conjugate pairs of each of the `values`,
+/// and if `bivalue` is set, the two possible values of every cell with exactly two of them.
+/// Exactly one end of each link must be true
+pub fn strong_links(board: &Board, values: &[u8], bivalue: bool) -> Vec<(Candidate, Candidate)> {
+    let mut links: Vec<(Candidate, Candidate)> = values
+        .iter()
+        .flat_map(|value| {
+            board
+                .conjugate_pairs(*value)
+                .into_iter()
+                .map(move |(a, b)| ((a, *value), (b, *value)))
+        })
+        .collect();
+    if bivalue {
+        for (pos, values) in board.cells_with_possible_count(2) {
+            if let Some((a, b)) = values.pair() {
+                links.push(((pos, a), (pos, b)));
+            }
+        }
+    }
+    links
+}
+
+/// Splits the graph made by `links` into its connected components, giving each candidate one of
+/// two alternating colors. Within a component every candidate of one color is true, or every
+/// candidate of the other color is
+pub fn color_components(links: &[(Candidate, Candidate)]) -> Vec<Vec<(Candidate, bool)>> {
+    let mut components: Vec<Vec<(Candidate, bool)>> = Vec::new();
+    let mut seen: Vec<Candidate> = Vec::new();
+
+    for (start, _end) in links {
+        if seen.contains(start) {
+            continue;
+        }
+        seen.push(*start);
+        let mut component = vec![(*start, true)];
+        let mut index = 0;
+        while index < component.len() {
+            let (node, color) = component[index];
+            index += 1;
+            for (a, b) in links {
+                let next = if *a == node {
+                    *b
+                } else if *b == node {
+                    *a
+                } else {
+                    continue;
+                };
+                if !seen.contains(&next) {
+                    seen.push(next);
+                    component.push((next, !color));
+                }
+            }
+        }
+        components.push(component);
+    }
+
+    components
+}
+
+/// Removes every candidate of `color` in the component, recording it in the step
+fn remove_color(board: &mut Board, step: &mut Step, component: &[(Candidate, bool)], color: bool) {
+    for ((pos, value), _color) in component.iter().filter(|(_node, c)| *c == color) {
+        step.eliminate(board, *pos, *value);
+    }
+}
+
+/// Returns a step for a colored component, listing its cells and values
+fn component_step(description: String, component: &[(Candidate, bool)]) -> Step {
+    let mut cells: Vec<(usize, usize)> = component.iter().map(|((pos, _), _)| *pos).collect();
+    cells.sort();
+    cells.dedup();
+    let mut digits: Vec<u8> = component.iter().map(|((_, value), _)| *value).collect();
+    digits.sort();
+    digits.dedup();
+    Step::new(description, cells, digits)
+}
+
+/// Colors the chains of conjugate pairs for each value. If two cells of the same color see each
+/// other that color must be false, so the value is removed from all of them (color wrap). Any
+/// other cell that sees both colors can't be the value either (color trap)
+/// This strategy is called Simple Coloring
+pub fn handle_simple_coloring(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+
+    for value in 1..=9 {
+        let links = strong_links(board, &[value], false);
+        for component in color_components(&links) {
+            // Color wrap
+            let wrapped = [true, false].into_iter().find(|color| {
+                component.iter().any(|((a, _), a_color)| {
+                    *a_color == *color
+                        && component
+                            .iter()
+                            .any(|((b, _), b_color)| *b_color == *color && Board::sees(*a, *b))
+                })
+            });
+            if let Some(color) = wrapped {
+                let mut step = component_step(
+                    format!("two cells of the same color for {value} see each other"),
+                    &component,
+                );
+                remove_color(board, &mut step, &component, color);
+                if step.record(log) {
+                    updated = true;
+                }
+                continue;
+            }
+
+            // Color trap
+            let mut step = component_step(
+                format!("one of the two colors for {value} must be true"),
+                &component,
+            );
+            for (pos, _value) in board
+                .candidates()
+                .into_iter()
+                .filter(|(pos, val)| *val == value && !component.iter().any(|((c, _), _)| c == pos))
+            {
+                let sees_color = |color: bool| {
+                    component
+                        .iter()
+                        .any(|((c, _), c_color)| *c_color == color && Board::sees(pos, *c))
+                };
+                if sees_color(true) && sees_color(false) {
+                    step.eliminate(board, pos, value);
+                }
+            }
+            if step.record(log) {
+                updated = true;
+            }
+        }
+    }
+
+    updated
+}
+
+/// Returns the color of the component that is contradicted, if any. A color is false if it
+/// appears twice in one cell, twice for the same value in cells that see each other, or if it
+/// would remove every possible value from an uncolored cell
+fn medusa_contradiction(board: &Board, component: &[(Candidate, bool)]) -> Option<bool> {
+    for (index, ((a, a_value), a_color)) in component.iter().enumerate() {
+        for ((b, b_value), b_color) in &component[index + 1..] {
+            if a_color != b_color {
+                continue;
+            }
+            // Twice in a cell, or twice in a house
+            if (a == b && a_value != b_value) || (a_value == b_value && Board::sees(*a, *b)) {
+                return Some(*a_color);
+            }
+        }
+    }
+
+    // Cell emptied by color
+    for row in 0..9 {
+        for col in 0..9 {
+            let Cell::Possible(values) = board.get(row, col) else {
+                continue;
+            };
+            if values.is_empty() || component.iter().any(|((pos, _), _)| *pos == (row, col)) {
+                continue;
+            }
+            for color in [true, false] {
+                let emptied = values.iter().all(|val| {
+                    component.iter().any(|((pos, c_value), c_color)| {
+                        *c_color == color && *c_value == val && Board::sees(*pos, (row, col))
+                    })
+                });
+                if emptied {
+                    return Some(color);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Colors the chains of strong links across all values, where both conjugate pairs and the two
+/// possible values of a cell form links. A contradicted color is removed entirely, otherwise any
+/// uncolored candidate that is ruled out by both colors is removed
+/// This strategy is called 3D Medusa
+pub fn handle_medusa(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+    let values: Vec<u8> = (1..=9).collect();
+    let links = strong_links(board, &values, true);
+
+    for component in color_components(&links) {
+        if let Some(color) = medusa_contradiction(board, &component) {
+            let mut step = component_step("one color leads to a contradiction".into(), &component);
+            remove_color(board, &mut step, &component, color);
+            if step.record(log) {
+                updated = true;
+            }
+            continue;
+        }
+
+        let mut step = component_step("one of the two colors must be true".into(), &component);
+
+        let color_of = |candidate: &Candidate| {
+            component
+                .iter()
+                .find(|(node, _)| node == candidate)
+                .map(|(_, color)| *color)
+        };
+        for (pos, value) in board.candidates() {
+            if color_of(&(pos, value)).is_some() {
+                continue;
+            }
+            // Colors of the other candidates in the same cell
+            let in_cell: Vec<bool> = component
+                .iter()
+                .filter(|((c, _), _)| *c == pos)
+                .map(|(_, color)| *color)
+                .collect();
+            // Colors of the same value in cells that see this one
+            let seen: Vec<bool> = component
+                .iter()
+                .filter(|((c, c_value), _)| *c_value == value && Board::sees(pos, *c))
+                .map(|(_, color)| *color)
+                .collect();
+
+            let both_in_cell = in_cell.contains(&true) && in_cell.contains(&false);
+            let both_seen = seen.contains(&true) && seen.contains(&false);
+            let cell_and_seen = in_cell.iter().any(|color| seen.contains(&!color));
+            if both_in_cell || both_seen || cell_and_seen {
+                step.eliminate(board, pos, value);
+            }
+        }
+        if step.record(log) {
+            updated = true;
+        }
+    }
+
+    updated
+}
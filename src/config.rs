@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+/// Settings that control how far the more expensive strategies are allowed to search
+#[derive(Debug, Clone)]
+pub struct SolverConfig {
+    /// The maximum number of links in a chain found by the chain strategies
+    pub max_chain_length: usize,
+    /// Whether to use strategies that are only valid if the puzzle has exactly one solution
+    pub assume_unique: bool,
+    /// The maximum number of rounds of singles the forcing strategies run after each trial value
+    pub forcing_depth: usize,
+    /// How long each forcing strategy may spend trying values before giving up. With no limit
+    /// they are bounded only by `forcing_depth`, so the result doesn't depend on the machine
+    pub forcing_time_limit: Option<Duration>,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            max_chain_length: 16,
+            assume_unique: false,
+            forcing_depth: 20,
+            forcing_time_limit: None,
+        }
+    }
+}
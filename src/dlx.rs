@@ -0,0 +1,291 @@
+use crate::board::{Board, Cell};
+
+/// The number of constraints: every cell has a value, and every row, col, and group has each
+/// value once
+const CONSTRAINTS: usize = 324;
+
+/// Returns the four constraints that placing `value` at the position satisfies
+fn constraints_of((row, col): (usize, usize), value: u8) -> [usize; 4] {
+    let value = value as usize - 1;
+    let group = row / 3 * 3 + col / 3;
+    [
+        row * 9 + col,
+        81 + row * 9 + value,
+        162 + col * 9 + value,
+        243 + group * 9 + value,
+    ]
+}
+
+/// A sparse exact cover matrix stored as a toroidal doubly linked list, as in Knuth's Dancing
+/// Links. Node 0 is the root, nodes 1 to 324 are the column headers, and the rest are the 1s of
+/// the matrix, four for each candidate placement
+struct Matrix {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// The column header of each node
+    column: Vec<usize>,
+    /// The index into `candidates` of each node's row
+    row: Vec<usize>,
+    /// The number of nodes left in each column, indexed by header
+    size: Vec<usize>,
+    /// The placement each row of the matrix stands for
+    candidates: Vec<((usize, usize), u8)>,
+}
+
+impl Matrix {
+    /// Builds the matrix for the board. Known cells get only the row for their value, and
+    /// unsolved cells get a row for each of their possible values
+    fn new(board: &Board) -> Self {
+        let headers = CONSTRAINTS + 1;
+        let mut matrix = Self {
+            left: (0..headers)
+                .map(|node| (node + headers - 1) % headers)
+                .collect(),
+            right: (0..headers).map(|node| (node + 1) % headers).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            row: vec![0; headers],
+            size: vec![0; headers],
+            candidates: Vec::new(),
+        };
+
+        for row in 0..9 {
+            for col in 0..9 {
+                match board.get(row, col) {
+                    Cell::Known(value) => matrix.add_row((row, col), *value),
+                    Cell::Possible(values) => {
+                        for value in values {
+                            matrix.add_row((row, col), value);
+                        }
+                    }
+                }
+            }
+        }
+        matrix
+    }
+
+    /// Adds a row for placing `value` at the position, linking a node into each column it covers
+    fn add_row(&mut self, pos: (usize, usize), value: u8) {
+        if !(1..=9).contains(&value) {
+            return;
+        }
+        let row = self.candidates.len();
+        self.candidates.push((pos, value));
+
+        let first = self.left.len();
+        for (offset, constraint) in constraints_of(pos, value).into_iter().enumerate() {
+            let node = first + offset;
+            let header = constraint + 1;
+            self.left.push(first + (offset + 3) % 4);
+            self.right.push(first + (offset + 1) % 4);
+            self.up.push(self.up[header]);
+            self.down.push(header);
+            self.column.push(header);
+            self.row.push(row);
+
+            let last = self.up[header];
+            self.down[last] = node;
+            self.up[header] = node;
+            self.size[header] += 1;
+        }
+    }
+
+    /// Removes the column from the header list and every row that has a node in it from the
+    /// other columns
+    fn cover(&mut self, header: usize) {
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = right;
+        self.left[right] = left;
+
+        let mut row = self.down[header];
+        while row != header {
+            let mut node = self.right[row];
+            while node != row {
+                let (up, down) = (self.up[node], self.down[node]);
+                self.down[up] = down;
+                self.up[down] = up;
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    /// Undoes `cover`, relinking everything in the reverse order it was removed
+    fn uncover(&mut self, header: usize) {
+        let mut row = self.up[header];
+        while row != header {
+            let mut node = self.left[row];
+            while node != row {
+                let (up, down) = (self.up[node], self.down[node]);
+                self.down[up] = node;
+                self.up[down] = node;
+                self.size[self.column[node]] += 1;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = header;
+        self.left[right] = header;
+    }
+
+    /// Returns the uncovered column with the fewest rows left, or None if every column is
+    /// covered
+    fn smallest_column(&self) -> Option<usize> {
+        let mut best = None;
+        let mut header = self.right[0];
+        while header != 0 {
+            if best.is_none_or(|best| self.size[header] < self.size[best]) {
+                best = Some(header);
+                if self.size[header] == 0 {
+                    break;
+                }
+            }
+            header = self.right[header];
+        }
+        best
+    }
+
+    /// Knuth's Algorithm X. Calls `found` with the rows of each exact cover, stopping as soon as
+    /// it returns false. Returns false if the search was stopped
+    fn search(
+        &mut self,
+        chosen: &mut Vec<usize>,
+        found: &mut impl FnMut(&[usize]) -> bool,
+    ) -> bool {
+        let Some(header) = self.smallest_column() else {
+            return found(chosen);
+        };
+        if self.size[header] == 0 {
+            return true;
+        }
+
+        self.cover(header);
+        let mut row = self.down[header];
+        while row != header {
+            chosen.push(self.row[row]);
+            let mut node = self.right[row];
+            while node != row {
+                self.cover(self.column[node]);
+                node = self.right[node];
+            }
+
+            let keep_going = self.search(chosen, found);
+
+            let mut node = self.left[row];
+            while node != row {
+                self.uncover(self.column[node]);
+                node = self.left[node];
+            }
+            chosen.pop();
+            if !keep_going {
+                self.uncover(header);
+                return false;
+            }
+            row = self.down[row];
+        }
+        self.uncover(header);
+        true
+    }
+}
+
+/// Runs Algorithm X over the board, calling `found` with each solution until it returns false
+fn each_solution(board: &Board, mut found: impl FnMut(Board) -> bool) {
+    let mut matrix = Matrix::new(board);
+    let candidates = matrix.candidates.clone();
+    matrix.search(&mut Vec::new(), &mut |rows| {
+        let mut solved = Board::default();
+        for row in rows {
+            let ((row, col), value) = candidates[*row];
+            *solved.get_mut(row, col) = Cell::Known(value);
+        }
+        found(solved)
+    });
+}
+
+/// Returns up to `limit` solutions of the board, found by encoding it as an exact cover problem
+/// and solving it with Dancing Links. Only the known values and possible values of the board are
+/// used, so any possible value that has already been removed stays removed
+pub fn dlx_solutions(board: &Board, limit: usize) -> Vec<Board> {
+    let mut solutions = Vec::new();
+    if limit == 0 {
+        return solutions;
+    }
+    each_solution(board, |solved| {
+        solutions.push(solved);
+        solutions.len() < limit
+    });
+    solutions
+}
+
+/// Returns the number of solutions of the board, counting no further than `limit`. Stops
+/// searching as soon as the limit is reached
+pub fn count_dlx_solutions(board: &Board, limit: usize) -> usize {
+    let mut count = 0;
+    if limit == 0 {
+        return count;
+    }
+    Matrix::new(board).search(&mut Vec::new(), &mut |_rows| {
+        count += 1;
+        count < limit
+    });
+    count
+}
+
+/// Returns the first solution of the board found with Dancing Links, or None if it has none
+pub fn solve_dlx(board: &Board) -> Option<Board> {
+    dlx_solutions(board, 1).pop()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::tests::{PUZZLE, SOLUTION};
+
+    #[test]
+    fn solves_a_known_puzzle() {
+        let puzzle: Board = PUZZLE.parse().unwrap();
+        let solution: Board = SOLUTION.parse().unwrap();
+        assert_eq!(dlx_solutions(&puzzle, 2), vec![solution.clone()]);
+        assert_eq!(solve_dlx(&puzzle), Some(solution));
+    }
+
+    #[test]
+    fn counts_no_solutions_for_a_broken_grid() {
+        // The first row repeats 3
+        let broken: Board = format!("3{}", &PUZZLE[1..]).parse().unwrap();
+        assert_eq!(count_dlx_solutions(&broken, 2), 0);
+        assert_eq!(solve_dlx(&broken), None);
+    }
+
+    #[test]
+    fn counts_one_solution_for_a_unique_grid() {
+        let puzzle: Board = PUZZLE.parse().unwrap();
+        assert_eq!(count_dlx_solutions(&puzzle, 2), 1);
+    }
+
+    #[test]
+    fn counts_two_solutions_for_an_under_constrained_grid() {
+        // The values of r8c3, r8c9, r9c3, and r9c9 can be swapped without breaking any house
+        let mut board: Board = SOLUTION.parse().unwrap();
+        for (row, col) in [(7, 2), (7, 8), (8, 2), (8, 8)] {
+            *board.get_mut(row, col) = Cell::default();
+        }
+        assert_eq!(count_dlx_solutions(&board, 3), 2);
+        assert_eq!(dlx_solutions(&board, 3).len(), 2);
+    }
+
+    #[test]
+    fn stops_at_the_limit() {
+        let empty = Board::default();
+        assert_eq!(count_dlx_solutions(&empty, 0), 0);
+        assert_eq!(count_dlx_solutions(&empty, 5), 5);
+        assert_eq!(dlx_solutions(&empty, 0).len(), 0);
+        assert_eq!(dlx_solutions(&empty, 3).len(), 3);
+    }
+}
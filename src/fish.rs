@@ -0,0 +1,185 @@
+use crate::{
+    board::Board,
+    step::{cell_names, Step},
+    subsets::make_combinations_from_valid_options,
+};
+
+/// Returns the positions of `value` along each row (or col) as `(line, crossing)` pairs, where
+/// `crossing` is the list of cols (or rows) the value is still possible in. Lines where the value
+/// is already known are skipped
+pub fn possible_positions(board: &Board, value: u8, by_rows: bool) -> Vec<(usize, Vec<usize>)> {
+    (0..9)
+        .filter_map(|line| {
+            let cells = if by_rows {
+                board.enum_row(line)
+            } else {
+                board.enum_col(line)
+            };
+            if cells.iter().any(|(_pos, cell)| cell.value() == Some(value)) {
+                return None;
+            }
+            let crossing = cells
+                .iter()
+                .filter(|(_pos, cell)| cell.is_possible(value))
+                .map(|((row, col), _cell)| if by_rows { *col } else { *row })
+                .collect();
+            Some((line, crossing))
+        })
+        .collect()
+}
+
+/// Converts a `(line, crossing)` pair back into a board position
+pub fn line_position(line: usize, crossing: usize, by_rows: bool) -> (usize, usize) {
+    if by_rows {
+        (line, crossing)
+    } else {
+        (crossing, line)
+    }
+}
+
+/// Returns the name of a list of rows (or cols), like "rows 1, 5"
+fn lines_name(lines: &[usize], rows: bool) -> String {
+    let numbers: Vec<String> = lines.iter().map(|line| (line + 1).to_string()).collect();
+    format!(
+        "{} {}",
+        if rows { "rows" } else { "cols" },
+        numbers.join(", ")
+    )
+}
+
+/// Returns the positions of `value` in the base lines of a fish
+fn base_cells(lines: &[(usize, Vec<usize>)], base: &[usize], by_rows: bool) -> Vec<(usize, usize)> {
+    base.iter()
+        .flat_map(|index| {
+            let (line, crossing) = &lines[*index];
+            crossing
+                .iter()
+                .map(move |crossing| line_position(*line, *crossing, by_rows))
+        })
+        .collect()
+}
+
+/// If a value is only possible in the same `size` cols across `size` rows, then those rows must
+/// contain the value in those cols, so remove it from every other row in those cols. The same
+/// applies with rows and cols swapped
+/// This strategy is called X-Wing (2), Swordfish (3), and Jellyfish (4)
+pub fn handle_fish(board: &mut Board, size: usize, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+
+    for value in 1..=9 {
+        for by_rows in [true, false] {
+            // Only lines with between 1 and `size` positions can be part of the fish
+            let lines: Vec<(usize, Vec<usize>)> = possible_positions(board, value, by_rows)
+                .into_iter()
+                .filter(|(_line, crossing)| !crossing.is_empty() && crossing.len() <= size)
+                .collect();
+            let indexes: Vec<usize> = (0..lines.len()).collect();
+
+            for base in make_combinations_from_valid_options(&indexes, size) {
+                let mut cover: Vec<usize> = base
+                    .iter()
+                    .flat_map(|index| lines[*index].1.iter().copied())
+                    .collect();
+                cover.sort();
+                cover.dedup();
+                if cover.len() != size {
+                    continue;
+                }
+
+                let base_lines: Vec<usize> = base.iter().map(|index| lines[*index].0).collect();
+                let mut step = Step::new(
+                    format!(
+                        "{value} in {} is only possible in {}",
+                        lines_name(&base_lines, by_rows),
+                        lines_name(&cover, !by_rows)
+                    ),
+                    base_cells(&lines, &base, by_rows),
+                    vec![value],
+                );
+                for crossing in cover {
+                    for line in (0..9).filter(|line| !base_lines.contains(line)) {
+                        step.eliminate(board, line_position(line, crossing, by_rows), value);
+                    }
+                }
+                if step.record(log) {
+                    updated = true;
+                }
+            }
+        }
+    }
+
+    updated
+}
+
+/// Like a normal fish, except some rows (or cols) have extra "fin" positions outside of the cover
+/// cols (or rows), all in the same group. Either a fin contains the value, or the fish holds, so
+/// the value can only be removed from cover cells that are in the fins' group. If removing the
+/// fins leaves a base line with at most one position the fish is called sashimi, which is handled
+/// the same way
+/// This strategy is called Finned/Sashimi X-Wing (2), Swordfish (3), and Jellyfish (4)
+pub fn handle_finned_fish(board: &mut Board, size: usize, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+
+    for value in 1..=9 {
+        for by_rows in [true, false] {
+            let lines: Vec<(usize, Vec<usize>)> = possible_positions(board, value, by_rows)
+                .into_iter()
+                .filter(|(_line, crossing)| !crossing.is_empty())
+                .collect();
+            let indexes: Vec<usize> = (0..lines.len()).collect();
+
+            for base in make_combinations_from_valid_options(&indexes, size) {
+                let base_lines: Vec<usize> = base.iter().map(|index| lines[*index].0).collect();
+                let crossings: Vec<usize> = base
+                    .iter()
+                    .flat_map(|index| lines[*index].1.iter().copied())
+                    .collect();
+
+                for cover in make_combinations_from_valid_options(&crossings, size) {
+                    let fins: Vec<(usize, usize)> = base
+                        .iter()
+                        .flat_map(|index| {
+                            let (line, crossing) = &lines[*index];
+                            crossing
+                                .iter()
+                                .filter(|crossing| !cover.contains(crossing))
+                                .map(move |crossing| line_position(*line, *crossing, by_rows))
+                        })
+                        .collect();
+                    if fins.is_empty() {
+                        continue;
+                    }
+                    let group = (fins[0].0 / 3, fins[0].1 / 3);
+                    if fins.iter().any(|(row, col)| (row / 3, col / 3) != group) {
+                        continue;
+                    }
+
+                    let mut step = Step::new(
+                        format!(
+                            "{value} in {} is only possible in {} or the fins {}",
+                            lines_name(&base_lines, by_rows),
+                            lines_name(&cover, !by_rows),
+                            cell_names(&fins)
+                        ),
+                        base_cells(&lines, &base, by_rows),
+                        vec![value],
+                    );
+                    for crossing in &cover {
+                        for line in (0..9).filter(|line| !base_lines.contains(line)) {
+                            let (row, col) = line_position(line, *crossing, by_rows);
+                            if (row / 3, col / 3) != group {
+                                continue;
+                            }
+                            step.eliminate(board, (row, col), value);
+                        }
+                    }
+                    if step.record(log) {
+                        updated = true;
+                    }
+                }
+            }
+        }
+    }
+
+    updated
+}
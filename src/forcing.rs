@@ -0,0 +1,200 @@
+use std::time::Instant;
+
+use crate::{
+    board::{Board, Cell},
+    candidates::Candidates,
+    config::SolverConfig,
+    singles::handle_singles,
+    step::{cell_name, house_name, Step},
+};
+
+/// Runs the singles for up to `config.forcing_depth` rounds, or until they stop updating the
+/// board
+fn propagate(board: &mut Board, config: &SolverConfig) {
+    for _ in 0..config.forcing_depth {
+        if board.contains_bad_cells() || !handle_singles(board, &mut Vec::new()) {
+            return;
+        }
+    }
+}
+
+/// Returns true if the forcing strategy that started at `start` has used up its time limit
+fn out_of_time(start: Instant, config: &SolverConfig) -> bool {
+    config
+        .forcing_time_limit
+        .is_some_and(|limit| start.elapsed() > limit)
+}
+
+/// Sets the cell to `value` on a copy of the board and propagates it. Returns the resulting
+/// board, or None if it leads to a contradiction
+fn trial(
+    board: &Board,
+    (row, col): (usize, usize),
+    value: u8,
+    config: &SolverConfig,
+) -> Option<Board> {
+    let mut trial = board.clone();
+    *trial.get_mut(row, col) = Cell::Known(value);
+    propagate(&mut trial, config);
+    if trial.contains_bad_cells() || !trial.is_correct() {
+        None
+    } else {
+        Some(trial)
+    }
+}
+
+/// Given the boards from every way a cell or house could be filled, one of them must be right.
+/// So any value that isn't possible in a cell in any of them is removed from the cell, solving it
+/// if every branch agrees on its value. Branches that led to a contradiction are left out, and if
+/// every branch did, nothing is changed. The changes are recorded in the step
+fn apply_branches(board: &mut Board, step: &mut Step, branches: &[Option<Board>]) {
+    let branches: Vec<&Board> = branches.iter().flatten().collect();
+    if branches.is_empty() {
+        return;
+    }
+
+    for row in 0..9 {
+        for col in 0..9 {
+            let Cell::Possible(values) = board.get(row, col) else {
+                continue;
+            };
+            let ruled_out: Vec<u8> = values
+                .iter()
+                .filter(|val| {
+                    branches.iter().all(|branch| {
+                        let cell = branch.get(row, col);
+                        !cell.is_possible(*val) && cell.value() != Some(*val)
+                    })
+                })
+                .collect();
+            for value in ruled_out {
+                step.eliminate(board, (row, col), value);
+            }
+        }
+    }
+}
+
+/// Returns every unsolved cell with its possible values
+fn unsolved(board: &Board) -> Vec<((usize, usize), Candidates)> {
+    (2..=9)
+        .flat_map(|count| board.cells_with_possible_count(count))
+        .collect()
+}
+
+/// Tries each possible value of every cell in turn, and if following the singles from it leads
+/// to a contradiction the value is removed
+/// This strategy is called Nishio
+pub fn handle_nishio(board: &mut Board, config: &SolverConfig, log: &mut Vec<Step>) -> bool {
+    let start = Instant::now();
+    let mut updated = false;
+
+    for (pos, values) in unsolved(board) {
+        for value in values {
+            if out_of_time(start, config) {
+                return updated;
+            }
+            if !board.get(pos.0, pos.1).is_possible(value) {
+                continue;
+            }
+            if trial(board, pos, value, config).is_none() {
+                let mut step = Step::new(
+                    format!("{} being {value} leads to a contradiction", cell_name(pos)),
+                    vec![pos],
+                    vec![value],
+                );
+                step.eliminate(board, pos, value);
+                if step.record(log) {
+                    updated = true;
+                }
+            }
+        }
+    }
+
+    updated
+}
+
+/// Tries every possible value of a cell. The cell must be one of them, so anything that all of
+/// the trials agree on is true
+/// This strategy is called Cell Forcing Chains
+pub fn handle_cell_forcing_chains(
+    board: &mut Board,
+    config: &SolverConfig,
+    log: &mut Vec<Step>,
+) -> bool {
+    let start = Instant::now();
+    let mut updated = false;
+
+    for (pos, _values) in unsolved(board) {
+        if out_of_time(start, config) {
+            return updated;
+        }
+        let Cell::Possible(values) = *board.get(pos.0, pos.1) else {
+            continue;
+        };
+        let branches: Vec<Option<Board>> = values
+            .iter()
+            .map(|value| trial(board, pos, value, config))
+            .collect();
+        let mut step = Step::new(
+            format!("every possible value of {} agrees", cell_name(pos)),
+            vec![pos],
+            values.to_vec(),
+        );
+        apply_branches(board, &mut step, &branches);
+        if step.record(log) {
+            updated = true;
+        }
+    }
+
+    updated
+}
+
+/// Tries every cell of a row, col, or group that could be a value. The value must be in one of
+/// them, so anything that all of the trials agree on is true
+/// This strategy is called Unit Forcing Chains
+pub fn handle_unit_forcing_chains(
+    board: &mut Board,
+    config: &SolverConfig,
+    log: &mut Vec<Step>,
+) -> bool {
+    let start = Instant::now();
+    let mut updated = false;
+
+    for house in Board::houses() {
+        for value in 1..=9 {
+            if out_of_time(start, config) {
+                return updated;
+            }
+            if house
+                .iter()
+                .any(|(row, col)| board.get(*row, *col).value() == Some(value))
+            {
+                continue;
+            }
+            let cells: Vec<(usize, usize)> = house
+                .iter()
+                .filter(|(row, col)| board.get(*row, *col).is_possible(value))
+                .copied()
+                .collect();
+            // A single cell is a hidden single, which the singles already handle
+            if cells.len() < 2 {
+                continue;
+            }
+            let branches: Vec<Option<Board>> = cells
+                .iter()
+                .map(|pos| trial(board, *pos, value, config))
+                .collect();
+            let mut step = Step::new(
+                format!("every position of {value} in {} agrees", house_name(house)),
+                cells,
+                vec![value],
+            );
+            apply_branches(board, &mut step, &branches);
+            if step.record(log) {
+                updated = true;
+            }
+        }
+    }
+
+    updated
+}
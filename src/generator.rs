@@ -0,0 +1,162 @@
+use crate::{
+    board::{Board, Cell},
+    config::SolverConfig,
+    minimizer::remove_givens,
+    rating::{rate, Level},
+    strategy::Pipeline,
+};
+
+/// A small pseudo random number generator (SplitMix64). The same seed always gives the same
+/// numbers, so generated puzzles can be reproduced
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Returns the next random number
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number from 0 up to but not including `bound`
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Puts the items in a random order
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.below(index + 1));
+        }
+    }
+}
+
+/// Which cells have to be given or removed together, so the pattern of givens looks the same
+/// after the grid is turned or flipped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Symmetry {
+    /// Any pattern of givens
+    None,
+    /// The pattern is the same after turning the grid half way round
+    #[default]
+    Rotational,
+    /// The pattern is the same after flipping the grid left to right
+    Mirror,
+}
+
+impl Symmetry {
+    /// Returns the position and every position it has to match
+    pub fn orbit(self, (row, col): (usize, usize)) -> Vec<(usize, usize)> {
+        let other = match self {
+            Symmetry::None => (row, col),
+            Symmetry::Rotational => (8 - row, 8 - col),
+            Symmetry::Mirror => (row, 8 - col),
+        };
+        if other == (row, col) {
+            vec![(row, col)]
+        } else {
+            vec![(row, col), other]
+        }
+    }
+}
+
+/// Settings for generating puzzles
+#[derive(Debug, Clone)]
+pub struct GeneratorConfig {
+    /// The seed for the random numbers. The same settings always generate the same puzzle
+    pub seed: u64,
+    pub symmetry: Symmetry,
+    /// The easiest level a generated puzzle may be
+    pub min_level: Level,
+    /// The hardest level a generated puzzle may be
+    pub max_level: Level,
+    /// How many full grids to try before giving up on the difficulty band
+    pub attempts: usize,
+    /// The settings for the strategies used to rate puzzles
+    pub solver: SolverConfig,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            symmetry: Symmetry::default(),
+            min_level: Level::Easy,
+            max_level: Level::Extreme,
+            attempts: 100,
+            solver: SolverConfig::default(),
+        }
+    }
+}
+
+/// Fills the cells from `index` onwards with random values that don't repeat in any row, col,
+/// or group, backtracking when a cell has no value left. Returns true if the grid was filled
+fn fill(board: &mut Board, rng: &mut Rng, index: usize) -> bool {
+    if index == 81 {
+        return true;
+    }
+    let (row, col) = (index / 9, index % 9);
+    let mut values: Vec<u8> = (1..=9)
+        .filter(|val| {
+            Board::peers(row, col)
+                .iter()
+                .all(|(peer_row, peer_col)| board.get(*peer_row, *peer_col).value() != Some(*val))
+        })
+        .collect();
+    rng.shuffle(&mut values);
+
+    for value in values {
+        *board.get_mut(row, col) = Cell::Known(value);
+        if fill(board, rng, index + 1) {
+            return true;
+        }
+    }
+    *board.get_mut(row, col) = Cell::default();
+    false
+}
+
+/// Returns a random completely filled grid
+pub fn random_grid(rng: &mut Rng) -> Board {
+    let mut board = Board::default();
+    fill(&mut board, rng, 0);
+    board
+}
+
+/// Generates a puzzle with exactly one solution, a pattern of givens that follows the symmetry,
+/// and a rating within the difficulty band. Givens are removed from random full grids until no
+/// more can go, and the first puzzle that is hard enough is returned. Returns None if none of
+/// the attempts were
+pub fn generate(config: &GeneratorConfig) -> Option<Board> {
+    let mut rng = Rng::new(config.seed);
+    // Only the strategies in the band are used, so a puzzle that needs anything harder can't be
+    // solved and is rated Extreme
+    let mut pipeline = Pipeline::new(&config.solver);
+    if config.max_level < Level::Extreme {
+        pipeline
+            .strategies
+            .retain(|strategy| Level::from_difficulty(strategy.difficulty()) <= config.max_level);
+    }
+
+    for _ in 0..config.attempts {
+        let grid = random_grid(&mut rng);
+        let mut positions: Vec<(usize, usize)> =
+            (0..81).map(|index| (index / 9, index % 9)).collect();
+        rng.shuffle(&mut positions);
+        // Without a limit any unique puzzle will do, so there's no need to rate each removal
+        let puzzle = remove_givens(&grid, &positions, config.symmetry, |puzzle| {
+            config.max_level == Level::Extreme || rate(puzzle, &pipeline).level <= config.max_level
+        });
+        let level = rate(&puzzle, &pipeline).level;
+        if (config.min_level..=config.max_level).contains(&level) {
+            return Some(puzzle);
+        }
+    }
+    None
+}
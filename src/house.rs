@@ -0,0 +1,122 @@
+use std::fmt;
+
+/// One of the 27 rows, cols, and groups of the board, each of which must contain the values 1 to
+/// 9 exactly once. Groups are numbered 0 to 8 across then down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum House {
+    Row(usize),
+    Col(usize),
+    Group(usize),
+}
+
+/// The positions of the cells in every house, rows first, then cols, then groups
+static HOUSE_CELLS: [[(usize, usize); 9]; 27] = house_cells();
+
+/// The positions of the 20 peers of every cell, indexed by `row * 9 + col`
+static PEERS: [[(usize, usize); 20]; 81] = peers();
+
+const fn house_cells() -> [[(usize, usize); 9]; 27] {
+    let mut houses = [[(0, 0); 9]; 27];
+    let mut house = 0;
+    while house < 9 {
+        let mut index = 0;
+        while index < 9 {
+            houses[house][index] = (house, index);
+            houses[house + 9][index] = (index, house);
+            houses[house + 18][index] = (house / 3 * 3 + index / 3, house % 3 * 3 + index % 3);
+            index += 1;
+        }
+        house += 1;
+    }
+    houses
+}
+
+/// Returns true if the two positions are different cells that share a row, col, or group. A const
+/// fn so the peer table can be built at compile time
+pub const fn sees(a: (usize, usize), b: (usize, usize)) -> bool {
+    !(a.0 == b.0 && a.1 == b.1)
+        && (a.0 == b.0 || a.1 == b.1 || (a.0 / 3 == b.0 / 3 && a.1 / 3 == b.1 / 3))
+}
+
+const fn peers() -> [[(usize, usize); 20]; 81] {
+    let mut peers = [[(0, 0); 20]; 81];
+    let mut cell = 0;
+    while cell < 81 {
+        let pos = (cell / 9, cell % 9);
+        let mut found = 0;
+        let mut other = 0;
+        while other < 81 {
+            let other_pos = (other / 9, other % 9);
+            if sees(pos, other_pos) {
+                peers[cell][found] = other_pos;
+                found += 1;
+            }
+            other += 1;
+        }
+        cell += 1;
+    }
+    peers
+}
+
+impl House {
+    /// Returns every house, rows first, then cols, then groups
+    pub fn all() -> impl Iterator<Item = House> {
+        (0..27).map(House::from_index)
+    }
+
+    /// Returns the house with the given index into `House::all`
+    pub fn from_index(index: usize) -> House {
+        match index / 9 {
+            0 => House::Row(index),
+            1 => House::Col(index - 9),
+            _ => House::Group(index - 18),
+        }
+    }
+
+    /// Returns the index of the house in `House::all`
+    pub fn index(self) -> usize {
+        match self {
+            House::Row(row) => row,
+            House::Col(col) => col + 9,
+            House::Group(group) => group + 18,
+        }
+    }
+
+    /// Returns the row, col, and group that contain the position
+    pub fn containing((row, col): (usize, usize)) -> [House; 3] {
+        [
+            House::Row(row),
+            House::Col(col),
+            House::Group(row / 3 * 3 + col / 3),
+        ]
+    }
+
+    /// Returns the positions of the cells in the house, in order
+    pub fn cells(self) -> &'static [(usize, usize); 9] {
+        &HOUSE_CELLS[self.index()]
+    }
+
+    /// Returns true if the position is one of the cells in the house
+    pub fn contains(self, (row, col): (usize, usize)) -> bool {
+        match self {
+            House::Row(index) => row == index,
+            House::Col(index) => col == index,
+            House::Group(index) => row / 3 * 3 + col / 3 == index,
+        }
+    }
+}
+
+impl fmt::Display for House {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            House::Row(row) => write!(f, "row {}", row + 1),
+            House::Col(col) => write!(f, "col {}", col + 1),
+            House::Group(group) => write!(f, "box {}", group + 1),
+        }
+    }
+}
+
+/// Returns the positions of the 20 cells that share a row, col, or group with the position
+pub fn peers_of((row, col): (usize, usize)) -> &'static [(usize, usize); 20] {
+    &PEERS[row * 9 + col]
+}
@@ -0,0 +1,340 @@
+use crate::{
+    board::{Board, Cell},
+    step::{group_name, Step},
+};
+
+// If the only possible positions for a value in a row are in the same group, remove that
+// possible number from all cells in the group outside the row
+// This strategy is the row equivalent of pointing pairs and tripples
+pub fn handle_blocking_row(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+
+    for row in 0..9 {
+        // List all known values in the row
+        let present: Vec<u8> = board
+            .row(row)
+            .iter()
+            .filter_map(|cell| cell.value())
+            .collect();
+        for missing in 1..=9 {
+            if present.contains(&missing) {
+                continue;
+            }
+            // List of all cells in the row that contain the missing value as a possible value
+            let found: Vec<(usize, usize)> = board
+                .enum_row(row)
+                .iter()
+                .filter_map(|(pos, cell)| match cell {
+                    Cell::Known(_) => None,
+                    Cell::Possible(possible) => {
+                        if possible.contains(missing) {
+                            Some(*pos)
+                        } else {
+                            None
+                        }
+                    }
+                })
+                .collect();
+
+            if found.is_empty() {
+                continue;
+            }
+
+            let group_row = found[0].0 / 3;
+            let group_col = found[0].1 / 3;
+
+            // If all the cells we found are in the same group, then we can remove that possible
+            // value from all cells in the group that are not in that row
+            if found
+                .iter()
+                .all(|(row, col)| row / 3 == group_row && col / 3 == group_col)
+            {
+                let mut step = Step::new(
+                    format!(
+                        "{missing} in row {} is only possible in {}",
+                        row + 1,
+                        group_name(group_row, group_col)
+                    ),
+                    found.clone(),
+                    vec![missing],
+                );
+                board
+                    .enum_group_mut(group_row, group_col)
+                    .into_iter()
+                    .for_each(|(pos, cell)| {
+                        if pos.0 == row {
+                            return;
+                        }
+                        step.remove(pos, cell, missing);
+                    });
+                if step.record(log) {
+                    updated = true;
+                }
+            }
+        }
+    }
+
+    updated
+}
+
+// If the only possible positions for a value in a col are in the same group, remove that
+// possible number from all cells in the group outside the col
+// This strategy is the col equivalent of pointing pairs and tripples
+pub fn handle_blocking_col(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+
+    for col in 0..9 {
+        // List all known values in the col
+        let present: Vec<u8> = board
+            .col(col)
+            .iter()
+            .filter_map(|cell| cell.value())
+            .collect();
+        for missing in 1..=9 {
+            if present.contains(&missing) {
+                continue;
+            }
+            // List of all cells in the col that contain the missing value as a possible value
+            let found: Vec<(usize, usize)> = board
+                .enum_col(col)
+                .iter()
+                .filter_map(|(pos, cell)| match cell {
+                    Cell::Known(_) => None,
+                    Cell::Possible(possible) => {
+                        if possible.contains(missing) {
+                            Some(*pos)
+                        } else {
+                            None
+                        }
+                    }
+                })
+                .collect();
+
+            if found.is_empty() {
+                continue;
+            }
+
+            let group_row = found[0].0 / 3;
+            let group_col = found[0].1 / 3;
+
+            // If all the cells we found are in the same group, then we can remove that possible
+            // value from all cells in the group that are not in that col
+            if found
+                .iter()
+                .all(|(row, col)| row / 3 == group_row && col / 3 == group_col)
+            {
+                let mut step = Step::new(
+                    format!(
+                        "{missing} in col {} is only possible in {}",
+                        col + 1,
+                        group_name(group_row, group_col)
+                    ),
+                    found.clone(),
+                    vec![missing],
+                );
+                board
+                    .enum_group_mut(group_row, group_col)
+                    .into_iter()
+                    .for_each(|(pos, cell)| {
+                        if pos.1 == col {
+                            return;
+                        }
+                        step.remove(pos, cell, missing);
+                    });
+                if step.record(log) {
+                    updated = true;
+                }
+            }
+        }
+    }
+
+    updated
+}
+
+/// If two groups in the same band of rows (or stack of cols) both only have a possible number in
+/// the same two rows (or cols), then those two groups must take that number in both rows, so it
+/// is removed from those rows (or cols) in the third group
+/// This strategy is called advanced pointing. It never finds anything claiming doesn't: the
+/// third row (or col) can then only have the number in the third group, and claiming removes
+/// the same values. So it isn't part of the default pipeline, but can be added to one with
+/// `Pipeline::add`
+pub fn handle_advanced_pointing(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+    for by_rows in [true, false] {
+        for band in 0..3 {
+            // The groups in the band, in order along it
+            let groups: Vec<(usize, usize)> = (0..3)
+                .map(|index| {
+                    if by_rows {
+                        (band, index)
+                    } else {
+                        (index, band)
+                    }
+                })
+                .collect();
+
+            for missing in 1..=9 {
+                // The rows or cols each group has the missing value in, or None if it's known
+                let lines: Vec<Option<Vec<usize>>> = groups
+                    .iter()
+                    .map(|(group_row, group_col)| {
+                        let group = board.enum_group(*group_row, *group_col);
+                        if group
+                            .iter()
+                            .any(|(_pos, cell)| cell.value() == Some(missing))
+                        {
+                            return None;
+                        }
+                        let mut lines: Vec<usize> = group
+                            .iter()
+                            .filter(|(_pos, cell)| cell.is_possible(missing))
+                            .map(|(pos, _cell)| if by_rows { pos.0 } else { pos.1 })
+                            .collect();
+                        lines.sort();
+                        lines.dedup();
+                        Some(lines)
+                    })
+                    .collect();
+
+                for (first, second, third) in [(0, 1, 2), (0, 2, 1), (1, 2, 0)] {
+                    let (Some(first_lines), Some(second_lines)) = (&lines[first], &lines[second])
+                    else {
+                        continue;
+                    };
+                    if first_lines.len() != 2 || first_lines != second_lines {
+                        continue;
+                    }
+                    let names: Vec<String> = [first, second]
+                        .iter()
+                        .map(|index| group_name(groups[*index].0, groups[*index].1))
+                        .collect();
+                    let lines_name = format!(
+                        "{} {} and {}",
+                        if by_rows { "rows" } else { "cols" },
+                        first_lines[0] + 1,
+                        first_lines[1] + 1
+                    );
+                    let cells: Vec<(usize, usize)> = [first, second]
+                        .iter()
+                        .flat_map(|index| board.enum_group(groups[*index].0, groups[*index].1))
+                        .filter(|(_pos, cell)| cell.is_possible(missing))
+                        .map(|(pos, _cell)| pos)
+                        .collect();
+                    let mut step = Step::new(
+                        format!(
+                            "{missing} in {} and {} is only possible in {lines_name}",
+                            names[0], names[1]
+                        ),
+                        cells,
+                        vec![missing],
+                    );
+                    let (group_row, group_col) = groups[third];
+                    for (pos, cell) in board.enum_group_mut(group_row, group_col) {
+                        let line = if by_rows { pos.0 } else { pos.1 };
+                        if first_lines.contains(&line) {
+                            step.remove(pos, cell, missing);
+                        }
+                    }
+                    if step.record(log) {
+                        updated = true;
+                    }
+                }
+            }
+        }
+    }
+    updated
+}
+
+/// If only a single row or col in a group contains cells with a possible number, remove that
+/// possible number from all cells in that row or col outside the group
+/// This strategy is called pointing pairs and tripples
+pub fn handle_pointing(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+    for group_row in 0..3 {
+        for group_col in 0..3 {
+            // List of all known values in the group
+            let present: Vec<u8> = board
+                .group(group_row, group_col)
+                .iter()
+                .filter_map(|cell| cell.value())
+                .collect();
+
+            for missing in 1..=9 {
+                if present.contains(&missing) {
+                    continue;
+                }
+                // List of all cells in the group that contain the missing value as a possible value
+                let found: Vec<(usize, usize)> = board
+                    .enum_group(group_row, group_col)
+                    .iter()
+                    .filter_map(|(pos, cell)| match cell {
+                        Cell::Known(_) => None,
+                        Cell::Possible(values) => {
+                            if values.contains(missing) {
+                                Some(*pos)
+                            } else {
+                                None
+                            }
+                        }
+                    })
+                    .collect();
+
+                if found.is_empty() {
+                    continue;
+                }
+
+                let row = found[0].0;
+                let col = found[0].1;
+
+                // If all the cells found are in the same row or col, then they're a 'pointing'
+                // pair or tripple and we'll remove them from all other cells in the row or col
+                let row_only = found.iter().all(|pos| pos.0 == row);
+                let col_only = found.iter().all(|pos| pos.1 == col);
+
+                // Handle a pointing pair/tripple in a row
+                if row_only {
+                    let mut step = Step::new(
+                        format!(
+                            "{missing} in {} is only possible in row {}",
+                            group_name(group_row, group_col),
+                            row + 1
+                        ),
+                        found.clone(),
+                        vec![missing],
+                    );
+                    for col in 0..9 {
+                        if found.iter().any(|pos| pos.1 == col) {
+                            continue;
+                        }
+                        step.eliminate(board, (row, col), missing);
+                    }
+                    if step.record(log) {
+                        updated = true;
+                    }
+                }
+                // Handle a pointing pair/tripple in a col
+                if col_only {
+                    let mut step = Step::new(
+                        format!(
+                            "{missing} in {} is only possible in col {}",
+                            group_name(group_row, group_col),
+                            col + 1
+                        ),
+                        found.clone(),
+                        vec![missing],
+                    );
+                    for row in 0..9 {
+                        if found.iter().any(|pos| pos.0 == row) {
+                            continue;
+                        }
+                        step.eliminate(board, (row, col), missing);
+                    }
+                    if step.record(log) {
+                        updated = true;
+                    }
+                }
+            }
+        }
+    }
+    updated
+}
@@ -0,0 +1,166 @@
+//! A sudoku solver that works through the techniques a person would use, cheapest first, with a
+//! guessing fallback for puzzles the techniques can't finish
+
+pub mod als;
+pub mod board;
+pub mod candidates;
+pub mod canonical;
+pub mod chains;
+pub mod coloring;
+pub mod config;
+pub mod dlx;
+pub mod fish;
+pub mod forcing;
+pub mod generator;
+pub mod house;
+pub mod intersections;
+pub mod minimizer;
+pub mod rating;
+pub mod single_digit;
+pub mod singles;
+pub mod step;
+pub mod strategy;
+pub mod subsets;
+pub mod sue_de_coq;
+pub mod uniqueness;
+pub mod wings;
+
+pub use board::{Board, Cell, ParseBoardError};
+pub use candidates::Candidates;
+pub use canonical::are_equivalent;
+pub use config::SolverConfig;
+pub use dlx::{count_dlx_solutions, dlx_solutions, solve_dlx};
+pub use generator::{generate, GeneratorConfig, Symmetry};
+pub use house::House;
+pub use minimizer::{minimize, redundant_givens};
+pub use rating::{rate, Level, Rating};
+pub use step::{SolveLog, Step};
+pub use strategy::{Pipeline, StepResult, Strategy, Technique};
+
+/// How far the strategies got with a board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveOutcome {
+    /// Every cell is known and no row, col, or group repeats a value
+    Solved,
+    /// No strategy could make any more progress
+    Stuck,
+    /// A cell has no possible values left, or a row, col, or group repeats a value
+    Contradiction,
+}
+
+/// Applies every built in strategy with the default settings to the board
+pub fn solve(board: &mut Board) -> SolveOutcome {
+    solve_with(board, &Pipeline::new(&SolverConfig::default()))
+}
+
+/// Applies the strategies in the pipeline to the board until it is solved, contains a
+/// contradiction, or none of them can make progress
+pub fn solve_with(board: &mut Board, pipeline: &Pipeline) -> SolveOutcome {
+    solve_with_log(board, pipeline).0
+}
+
+/// Like `solve_with`, but also returns every step the strategies took, in order
+pub fn solve_with_log(board: &mut Board, pipeline: &Pipeline) -> (SolveOutcome, SolveLog) {
+    let log = pipeline.run(board);
+    let outcome = if board.contains_bad_cells() || !board.is_correct() {
+        SolveOutcome::Contradiction
+    } else if board.num_unsolved() > 0 {
+        SolveOutcome::Stuck
+    } else {
+        SolveOutcome::Solved
+    };
+    (outcome, log)
+}
+
+/// Solves the board by guessing a value for the unsolved cell with the fewest possible values
+/// and applying the strategies to each guess, discarding any guess that leads to a contradiction.
+/// Only the singles and intersections are applied after each guess, since the harder strategies
+/// cost far more than the guesses they save. Returns the solved board, or None if the board has
+/// no solution
+pub fn solve_with_guessing(board: Board) -> Option<Board> {
+    guess(board, &Pipeline::basic())
+}
+
+/// Applies the pipeline to the board, then tries each value of the most constrained cell in turn
+fn guess(mut board: Board, pipeline: &Pipeline) -> Option<Board> {
+    if solve_with(&mut board, pipeline) == SolveOutcome::Contradiction {
+        return None;
+    }
+
+    let Some((row, col)) = board.most_constrained() else {
+        return Some(board);
+    };
+    let Cell::Possible(values) = board.get(row, col) else {
+        unreachable!("most_constrained only returns unsolved cells");
+    };
+
+    for value in *values {
+        let mut guess = board.clone();
+        *guess.get_mut(row, col) = Cell::Known(value);
+        if let Some(solved) = self::guess(guess, pipeline) {
+            return Some(solved);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::tests::SOLUTION;
+
+    /// Returns the solution with the cells at the indices emptied
+    fn solution_without(indices: &[usize]) -> Board {
+        SOLUTION
+            .char_indices()
+            .map(|(index, digit)| if indices.contains(&index) { '0' } else { digit })
+            .collect::<String>()
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn solves_a_puzzle_with_one_cell_missing_from_each_row() {
+        let givens = solution_without(&[0, 10, 20, 30, 40, 50, 60, 70, 80]);
+        let mut board = givens.clone();
+        assert_eq!(solve(&mut board), SolveOutcome::Solved);
+        assert_eq!(board, SOLUTION.parse().unwrap());
+
+        let mut board = givens;
+        let pipeline = Pipeline::new(&SolverConfig::default());
+        let (outcome, log) = solve_with_log(&mut board, &pipeline);
+        assert_eq!(outcome, SolveOutcome::Solved);
+        assert!(!log.steps.is_empty());
+        assert!(log.steps.iter().all(|step| step.technique == "Singles"));
+    }
+
+    #[test]
+    fn gets_stuck_on_a_deadly_pattern() {
+        // The two rows and cols of the empty cells can take their values either way round, so
+        // no strategy can choose between them
+        let mut board = solution_without(&[65, 71, 74, 80]);
+        assert_eq!(solve(&mut board), SolveOutcome::Stuck);
+        assert_eq!(board.num_unsolved(), 4);
+
+        let mut board = solution_without(&[65, 71, 74, 80]);
+        let pipeline = Pipeline::new(&SolverConfig::default());
+        let (outcome, log) = solve_with_log(&mut board, &pipeline);
+        assert_eq!(outcome, SolveOutcome::Stuck);
+        assert!(log.steps.iter().all(|step| step.technique == "Singles"));
+        assert_eq!(board.num_possible_values(), 8);
+    }
+
+    #[test]
+    fn finds_a_contradiction_when_a_value_repeats() {
+        // The first row and the second col both repeat 6
+        let givens: Board = format!("06{}", &SOLUTION[2..]).parse().unwrap();
+        let mut board = givens.clone();
+        assert_eq!(solve(&mut board), SolveOutcome::Contradiction);
+
+        let mut board = givens;
+        let pipeline = Pipeline::new(&SolverConfig::default());
+        let (outcome, _log) = solve_with_log(&mut board, &pipeline);
+        assert_eq!(outcome, SolveOutcome::Contradiction);
+    }
+}
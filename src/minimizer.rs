@@ -0,0 +1,126 @@
+use crate::{
+    board::{Board, Cell},
+    generator::Symmetry,
+};
+
+/// Returns a copy of the board with only its known values, so that possible values removed
+/// while solving don't count as clues
+fn givens_of(board: &Board) -> Board {
+    let mut puzzle = Board::default();
+    for row in 0..9 {
+        for col in 0..9 {
+            if let Cell::Known(value) = board.get(row, col) {
+                *puzzle.get_mut(row, col) = Cell::Known(*value);
+            }
+        }
+    }
+    puzzle
+}
+
+/// Goes through the positions in order, removing each given along with the givens that match it
+/// under the symmetry. A removal is kept only if the puzzle still has exactly one solution and
+/// `accept` returns true for it. Returns the puzzle once every position has been tried
+pub fn remove_givens(
+    puzzle: &Board,
+    positions: &[(usize, usize)],
+    symmetry: Symmetry,
+    accept: impl Fn(&Board) -> bool,
+) -> Board {
+    let mut puzzle = puzzle.clone();
+    for (row, col) in positions {
+        if !puzzle.get(*row, *col).is_known() {
+            continue;
+        }
+        let mut attempt = puzzle.clone();
+        for (row, col) in symmetry.orbit((*row, *col)) {
+            *attempt.get_mut(row, col) = Cell::default();
+        }
+        if attempt.has_unique_solution() && accept(&attempt) {
+            puzzle = attempt;
+        }
+    }
+    puzzle
+}
+
+/// Returns every given that could be removed on its own with the puzzle keeping exactly one
+/// solution. Returns nothing if the puzzle doesn't have exactly one solution to begin with
+pub fn redundant_givens(board: &Board) -> Vec<(usize, usize)> {
+    let puzzle = givens_of(board);
+    if !puzzle.has_unique_solution() {
+        return Vec::new();
+    }
+
+    let mut redundant = Vec::new();
+    for row in 0..9 {
+        for col in 0..9 {
+            if !puzzle.get(row, col).is_known() {
+                continue;
+            }
+            let mut attempt = puzzle.clone();
+            *attempt.get_mut(row, col) = Cell::default();
+            if attempt.has_unique_solution() {
+                redundant.push((row, col));
+            }
+        }
+    }
+    redundant
+}
+
+/// Removes givens from the puzzle until none can be removed without it having more than one
+/// solution. Givens that match under the symmetry are removed together, so a symmetric puzzle
+/// stays symmetric. Givens are tried top to bottom, left to right, so the same puzzle always
+/// gives the same result. Returns the givens unchanged if the puzzle doesn't have exactly one
+/// solution
+pub fn minimize(board: &Board, symmetry: Symmetry) -> Board {
+    let puzzle = givens_of(board);
+    if !puzzle.has_unique_solution() {
+        return puzzle;
+    }
+    let positions: Vec<(usize, usize)> = (0..81).map(|index| (index / 9, index % 9)).collect();
+    remove_givens(&puzzle, &positions, symmetry, |_puzzle| true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::tests::{PUZZLE, SOLUTION};
+
+    #[test]
+    fn finds_the_one_redundant_given() {
+        // The puzzle is already minimal, so the only given that can go is the one added to it
+        let mut puzzle: Board = PUZZLE.parse().unwrap();
+        *puzzle.get_mut(4, 4) = Cell::Known(4);
+        assert_eq!(redundant_givens(&puzzle), vec![(4, 4)]);
+        assert!(redundant_givens(&PUZZLE.parse().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn leaves_a_puzzle_without_a_unique_solution_alone() {
+        let mut puzzle: Board = PUZZLE.parse().unwrap();
+        *puzzle.get_mut(0, 6) = Cell::default();
+        assert!(!puzzle.has_unique_solution());
+        assert!(redundant_givens(&puzzle).is_empty());
+        assert_eq!(minimize(&puzzle, Symmetry::None), puzzle);
+    }
+
+    #[test]
+    fn minimized_puzzles_are_unique_and_minimal() {
+        let minimal = minimize(&SOLUTION.parse().unwrap(), Symmetry::None);
+        assert!(minimal.has_unique_solution());
+        assert!(redundant_givens(&minimal).is_empty());
+    }
+
+    #[test]
+    fn minimizing_with_rotational_symmetry_stays_symmetric() {
+        let minimal = minimize(&SOLUTION.parse().unwrap(), Symmetry::Rotational);
+        assert!(minimal.has_unique_solution());
+        for row in 0..9 {
+            for col in 0..9 {
+                assert_eq!(
+                    minimal.get(row, col).is_known(),
+                    minimal.get(8 - row, 8 - col).is_known()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+
+use crate::{board::Board, solve_with_log, step::SolveLog, strategy::Pipeline, SolveOutcome};
+
+/// How hard a puzzle is for a person, from the hardest technique it needs. These follow the
+/// levels HoDoKu uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// Only singles are needed
+    Easy,
+    /// Needs intersections or small subsets
+    Medium,
+    /// Needs quads, fish, wings, or coloring
+    Hard,
+    /// Needs chains, Sue de Coq, or almost locked sets
+    Unfair,
+    /// Needs forcing chains, or can't be solved by the strategies at all
+    Extreme,
+}
+
+impl Level {
+    /// Returns the level of a technique with the given difficulty
+    pub fn from_difficulty(difficulty: u32) -> Self {
+        match difficulty {
+            0..=10 => Level::Easy,
+            11..=100 => Level::Medium,
+            101..=200 => Level::Hard,
+            201..=400 => Level::Unfair,
+            _ => Level::Extreme,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Easy => "Easy",
+            Level::Medium => "Medium",
+            Level::Hard => "Hard",
+            Level::Unfair => "Unfair",
+            Level::Extreme => "Extreme",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The grade of a puzzle and the techniques it took to solve it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rating {
+    pub level: Level,
+    /// Whether the strategies solved the puzzle. If they didn't, the level is Extreme
+    pub solved: bool,
+    /// The hardest technique used, or None if no step was needed
+    pub hardest: Option<String>,
+    /// The difficulty of the hardest technique used
+    pub difficulty: u32,
+    /// The difficulty of every counted step added together, so puzzles that need a hard technique
+    /// many times score higher than ones that need it once
+    pub score: u32,
+    /// How many steps each technique took, in the order they were first used. Singles steps that
+    /// only clear the values already known in a house aren't counted, since every puzzle starts
+    /// with those
+    pub counts: Vec<(String, usize)>,
+}
+
+impl Rating {
+    /// Grades a solve from its outcome and log. The difficulty of each step is looked up by its
+    /// technique in the pipeline that took it
+    pub fn from_log(outcome: SolveOutcome, log: &SolveLog, pipeline: &Pipeline) -> Self {
+        let mut hardest = None;
+        let mut difficulty = 0;
+        let mut score = 0;
+        let mut counts: Vec<(String, usize)> = Vec::new();
+
+        for step in &log.steps {
+            // Clearing the values a house already has is bookkeeping, not a use of the technique
+            if step.technique == "Singles" && step.placements.is_empty() {
+                continue;
+            }
+            let step_difficulty = pipeline.difficulty_of(&step.technique).unwrap_or(0);
+            score += step_difficulty;
+            if hardest.is_none() || step_difficulty > difficulty {
+                hardest = Some(step.technique.clone());
+                difficulty = step_difficulty;
+            }
+            match counts
+                .iter_mut()
+                .find(|(name, _count)| *name == step.technique)
+            {
+                Some((_name, count)) => *count += 1,
+                None => counts.push((step.technique.clone(), 1)),
+            }
+        }
+
+        let solved = outcome == SolveOutcome::Solved;
+        let level = if solved {
+            Level::from_difficulty(difficulty)
+        } else {
+            Level::Extreme
+        };
+        Self {
+            level,
+            solved,
+            hardest,
+            difficulty,
+            score,
+            counts,
+        }
+    }
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (score {}", self.level, self.score)?;
+        if let Some(hardest) = &self.hardest {
+            write!(f, ", hardest: {hardest}")?;
+        }
+        if !self.solved {
+            write!(f, ", not solved by the strategies")?;
+        }
+        writeln!(f, ")")?;
+        for (name, count) in &self.counts {
+            writeln!(f, "  {name}: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Grades a puzzle by solving a copy of it with the strategies in the pipeline
+pub fn rate(board: &Board, pipeline: &Pipeline) -> Rating {
+    let mut board = board.clone();
+    let (outcome, log) = solve_with_log(&mut board, pipeline);
+    Rating::from_log(outcome, &log, pipeline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{board::tests::SOLUTION, config::SolverConfig};
+
+    #[test]
+    fn counts_only_singles_that_place_a_value() {
+        // One cell is missing from each row, so each one takes a single step to fill
+        let givens: String = SOLUTION
+            .char_indices()
+            .map(|(index, digit)| if index % 10 == 0 { '0' } else { digit })
+            .collect();
+        let pipeline = Pipeline::new(&SolverConfig::default());
+        let rating = rate(&givens.parse().unwrap(), &pipeline);
+        assert_eq!(rating.level, Level::Easy);
+        assert_eq!(rating.counts, vec![("Singles".to_string(), 9)]);
+        assert_eq!(rating.score, 90);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{
+    board::Board,
+    fish::{line_position, possible_positions},
+    step::{cell_name, group_name, Step},
+};
+
+/// Returns the conjugate pairs of `value` that are the only two positions in a row (or col), as
+/// `(line, [crossing, crossing])`
+fn line_pairs(board: &Board, value: u8, by_rows: bool) -> Vec<(usize, [usize; 2])> {
+    possible_positions(board, value, by_rows)
+        .into_iter()
+        .filter(|(_line, crossing)| crossing.len() == 2)
+        .map(|(line, crossing)| (line, [crossing[0], crossing[1]]))
+        .collect()
+}
+
+/// Returns a step for a pattern where one of the cells `a` and `b` must be `value`
+fn either_step(
+    cells: Vec<(usize, usize)>,
+    a: (usize, usize),
+    b: (usize, usize),
+    value: u8,
+) -> Step {
+    Step::new(
+        format!("{value} must be in {} or {}", cell_name(a), cell_name(b)),
+        cells,
+        vec![value],
+    )
+}
+
+/// If two rows each have only two positions for a value, and one position from each row is in
+/// the same col, then one of the other two positions must be the value, so remove it from every
+/// cell that sees both of them. The same applies with rows and cols swapped
+/// This strategy is called Skyscraper
+pub fn handle_skyscraper(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+
+    for value in 1..=9 {
+        for by_rows in [true, false] {
+            let pairs = line_pairs(board, value, by_rows);
+            for (index, (line_a, crossing_a)) in pairs.iter().enumerate() {
+                for (line_b, crossing_b) in &pairs[index + 1..] {
+                    for (base_a, end_a) in [(0, 1), (1, 0)] {
+                        for (base_b, end_b) in [(0, 1), (1, 0)] {
+                            // The bases share a line, but the ends don't, otherwise this would be
+                            // an X-Wing
+                            if crossing_a[base_a] != crossing_b[base_b]
+                                || crossing_a[end_a] == crossing_b[end_b]
+                            {
+                                continue;
+                            }
+                            let a = line_position(*line_a, crossing_a[end_a], by_rows);
+                            let b = line_position(*line_b, crossing_b[end_b], by_rows);
+                            let cells = vec![
+                                line_position(*line_a, crossing_a[base_a], by_rows),
+                                a,
+                                line_position(*line_b, crossing_b[base_b], by_rows),
+                                b,
+                            ];
+                            let mut step = either_step(cells, a, b, value);
+                            step.eliminate_from_common_peers(board, &[a, b], value);
+                            if step.record(log) {
+                                updated = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    updated
+}
+
+/// If a row and a col each have only two positions for a value, and one position from each is in
+/// the same group, then one of the other two positions must be the value, so remove it from every
+/// cell that sees both of them
+/// This strategy is called 2-String Kite
+pub fn handle_two_string_kite(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+
+    for value in 1..=9 {
+        let row_pairs = line_pairs(board, value, true);
+        let col_pairs = line_pairs(board, value, false);
+        for (row, row_crossing) in &row_pairs {
+            for (col, col_crossing) in &col_pairs {
+                let row_cells = row_crossing.map(|crossing| (*row, crossing));
+                let col_cells = col_crossing.map(|crossing| (crossing, *col));
+                for (base_row, end_row) in [(0, 1), (1, 0)] {
+                    for (base_col, end_col) in [(0, 1), (1, 0)] {
+                        let (row_base, row_end) = (row_cells[base_row], row_cells[end_row]);
+                        let (col_base, col_end) = (col_cells[base_col], col_cells[end_col]);
+                        // The bases must be different cells in the same group, with the ends
+                        // outside of it
+                        let same_group =
+                            row_base.0 / 3 == col_base.0 / 3 && row_base.1 / 3 == col_base.1 / 3;
+                        let cells = [row_base, row_end, col_base, col_end];
+                        let distinct = cells
+                            .iter()
+                            .enumerate()
+                            .all(|(index, cell)| !cells[index + 1..].contains(cell));
+                        if !same_group || !distinct {
+                            continue;
+                        }
+                        let mut step = either_step(cells.to_vec(), row_end, col_end, value);
+                        step.eliminate_from_common_peers(board, &[row_end, col_end], value);
+                        if step.record(log) {
+                            updated = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    updated
+}
+
+/// If the possible positions for a value in a group all lie in one row and one col of the group
+/// (and not just one of them), then whenever the row is ruled out the value must be in the col,
+/// and the other way around. Combined with a conjugate pair that has one end in that row, the
+/// other end and the group's col meet at a cell that can't be the value. The same applies with
+/// rows and cols swapped
+/// This strategy is called Empty Rectangle
+pub fn handle_empty_rectangle(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+
+    for value in 1..=9 {
+        for group_row in 0..3 {
+            for group_col in 0..3 {
+                let group = board.enum_group(group_row, group_col);
+                if group.iter().any(|(_pos, cell)| cell.value() == Some(value)) {
+                    continue;
+                }
+                let found: Vec<(usize, usize)> = group
+                    .iter()
+                    .filter(|(_pos, cell)| cell.is_possible(value))
+                    .map(|(pos, _cell)| *pos)
+                    .collect();
+                if found.len() < 2
+                    || found.iter().all(|pos| pos.0 == found[0].0)
+                    || found.iter().all(|pos| pos.1 == found[0].1)
+                {
+                    continue;
+                }
+
+                for er_row in group_row * 3..group_row * 3 + 3 {
+                    for er_col in group_col * 3..group_col * 3 + 3 {
+                        if !found.iter().all(|pos| pos.0 == er_row || pos.1 == er_col) {
+                            continue;
+                        }
+                        // Conjugate pairs in cols outside of the group's stack with one end
+                        // in the rectangle's row, and rows outside of the group's band with one
+                        // end in the rectangle's col
+                        for by_rows in [false, true] {
+                            let (er_line, er_crossing, group_line, group_crossing) = if by_rows {
+                                (er_col, er_row, group_row, group_col)
+                            } else {
+                                (er_row, er_col, group_col, group_row)
+                            };
+                            for (line, crossing) in line_pairs(board, value, by_rows) {
+                                if line / 3 == group_line {
+                                    continue;
+                                }
+                                for (near, far) in [(0, 1), (1, 0)] {
+                                    if crossing[near] != er_line
+                                        || crossing[far] / 3 == group_crossing
+                                    {
+                                        continue;
+                                    }
+                                    let near_cell = line_position(line, crossing[near], by_rows);
+                                    let far_cell = line_position(line, crossing[far], by_rows);
+                                    let mut cells = found.clone();
+                                    cells.extend([near_cell, far_cell]);
+                                    let mut step = Step::new(
+                                        format!(
+                                            "{value} in {} is only possible in row {} or col {}, \
+                                             and must be in {} or {}",
+                                            group_name(group_row, group_col),
+                                            er_row + 1,
+                                            er_col + 1,
+                                            cell_name(near_cell),
+                                            cell_name(far_cell)
+                                        ),
+                                        cells,
+                                        vec![value],
+                                    );
+                                    let target =
+                                        line_position(crossing[far], er_crossing, !by_rows);
+                                    step.eliminate(board, target, value);
+                                    if step.record(log) {
+                                        updated = true;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    updated
+}
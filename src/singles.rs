@@ -0,0 +1,96 @@
+use crate::{
+    board::{Board, Cell},
+    house::House,
+    step::{house_name, value_names, Step},
+};
+
+/// Removes the values known in a row, col, or group from the possible values of its other cells,
+/// and solves any value that is only possible in one cell. Returns true if any cell was updated
+pub fn handle_collection(mut cells: [((usize, usize), &mut Cell); 9], log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+    let positions: Vec<(usize, usize)> = cells.iter().map(|(pos, _cell)| *pos).collect();
+    let house = house_name(&positions);
+    // Get a list of all values currently known in the collection
+    let mut present: Vec<u8> = cells
+        .iter()
+        .filter_map(|(_pos, cell)| cell.value())
+        .collect();
+    let mut step = Step::new(
+        "",
+        cells
+            .iter()
+            .filter(|(_pos, cell)| cell.is_known())
+            .map(|(pos, _cell)| *pos)
+            .collect(),
+        Vec::new(),
+    );
+    // Remove the known present values from the list of possible values for all cells in this
+    // collection
+    for (pos, cell) in &mut cells {
+        let known = cell.is_known();
+        for value in present.clone() {
+            step.remove(*pos, cell, value);
+        }
+        cell.check();
+        if let (false, Some(value)) = (known, cell.value()) {
+            present.push(value);
+            if !step.placements.contains(&(*pos, value)) {
+                step.placements.push((*pos, value));
+            }
+        }
+    }
+    step.digits = present.clone();
+    step.digits.sort();
+    step.description = format!("{house} already has {}", value_names(&step.digits));
+    if step.record(log) {
+        updated = true;
+    }
+
+    // If only one cell in a collection has a value listed as possible, that cell must be that
+    // value
+    for missing in 1..=9 {
+        if present.contains(&missing) {
+            continue;
+        }
+        let mut possible_match = 0;
+        for (_pos, cell) in &cells {
+            match cell {
+                Cell::Known(_) => {}
+                Cell::Possible(possible) => {
+                    if possible.contains(missing) {
+                        possible_match += 1;
+                    }
+                }
+            }
+        }
+        if possible_match == 1 {
+            for (pos, cell) in &mut cells {
+                if cell.is_possible(missing) {
+                    let mut step = Step::new(
+                        format!("{missing} is only possible in one cell of {house}"),
+                        vec![*pos],
+                        vec![missing],
+                    );
+                    step.place(*pos, cell, missing);
+                    if step.record(log) {
+                        updated = true;
+                    }
+                }
+            }
+            present.push(missing);
+        }
+    }
+
+    updated
+}
+
+/// Finds naked and hidden singles in every row, col, and group
+pub fn handle_singles(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+    for house in House::all() {
+        if handle_collection(board.house_mut(house), log) {
+            updated = true;
+        }
+    }
+    updated
+}
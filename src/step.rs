@@ -0,0 +1,188 @@
+use std::fmt;
+
+use crate::{
+    board::{Board, Cell},
+    candidates::Candidate,
+    house::House,
+};
+
+/// Returns the name of a cell as it is usually written, like r1c7 for the top row and seventh col
+pub fn cell_name((row, col): (usize, usize)) -> String {
+    format!("r{}c{}", row + 1, col + 1)
+}
+
+/// Returns the names of the cells, separated by commas
+pub fn cell_names(cells: &[(usize, usize)]) -> String {
+    cells
+        .iter()
+        .map(|pos| cell_name(*pos))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns the values separated by commas
+pub fn value_names(values: &[u8]) -> String {
+    values
+        .iter()
+        .map(|val| val.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns the name of the row, col, or group that contains all of the cells, checking rows
+/// first. Groups are numbered 1 to 9 across then down
+pub fn house_name(cells: &[(usize, usize)]) -> String {
+    let Some(first) = cells.first() else {
+        return String::new();
+    };
+    let [row, col, group] = House::containing(*first);
+    [row, col]
+        .into_iter()
+        .find(|house| cells.iter().all(|pos| house.contains(*pos)))
+        .unwrap_or(group)
+        .to_string()
+}
+
+/// Returns the name of a group from its position in the grid of groups
+pub fn group_name(group_row: usize, group_col: usize) -> String {
+    format!("box {}", group_row * 3 + group_col + 1)
+}
+
+/// A single use of a technique: the pattern that was found and the changes it made to the board
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    /// The name of the technique, filled in by the strategy that found the step
+    pub technique: String,
+    /// Why the changes are valid, like "4 in box 2 is only possible in row 1"
+    pub description: String,
+    /// The cells that make up the pattern
+    pub cells: Vec<(usize, usize)>,
+    /// The values the pattern is about
+    pub digits: Vec<u8>,
+    /// Values that were placed, including cells left with one possible value
+    pub placements: Vec<Candidate>,
+    /// Possible values that were removed
+    pub eliminations: Vec<Candidate>,
+}
+
+impl Step {
+    pub fn new(
+        description: impl Into<String>,
+        cells: Vec<(usize, usize)>,
+        digits: Vec<u8>,
+    ) -> Self {
+        Self {
+            technique: String::new(),
+            description: description.into(),
+            cells,
+            digits,
+            placements: Vec::new(),
+            eliminations: Vec::new(),
+        }
+    }
+
+    /// Removes `value` from the cell and records it. Returns true if the value was removed
+    pub fn remove(&mut self, pos: (usize, usize), cell: &mut Cell, value: u8) -> bool {
+        if !cell.remove_possible(value) {
+            return false;
+        }
+        self.eliminations.push((pos, value));
+        if let Some(known) = cell.value() {
+            self.placements.push((pos, known));
+        }
+        true
+    }
+
+    /// Removes `value` from the cell at `pos` on the board and records it. Returns true if the
+    /// value was removed
+    pub fn eliminate(&mut self, board: &mut Board, pos: (usize, usize), value: u8) -> bool {
+        self.remove(pos, board.get_mut(pos.0, pos.1), value)
+    }
+
+    /// Sets the cell to `value` and records it. Returns true if the cell wasn't already known
+    pub fn place(&mut self, pos: (usize, usize), cell: &mut Cell, value: u8) -> bool {
+        if cell.is_known() {
+            return false;
+        }
+        *cell = Cell::Known(value);
+        self.placements.push((pos, value));
+        true
+    }
+
+    /// Removes `value` from every cell that sees all of `cells`, other than the cells themselves,
+    /// and records it. Returns true if any value was removed
+    pub fn eliminate_from_common_peers(
+        &mut self,
+        board: &mut Board,
+        cells: &[(usize, usize)],
+        value: u8,
+    ) -> bool {
+        let Some(first) = cells.first() else {
+            return false;
+        };
+        let mut removed = false;
+        for &pos in Board::peers(first.0, first.1) {
+            if !cells.iter().all(|cell| Board::sees(*cell, pos)) {
+                continue;
+            }
+            if self.eliminate(board, pos, value) {
+                removed = true;
+            }
+        }
+        removed
+    }
+
+    /// Adds the step to the log if it changed the board. Returns true if it did
+    pub fn record(self, log: &mut Vec<Step>) -> bool {
+        if self.placements.is_empty() && self.eliminations.is_empty() {
+            return false;
+        }
+        log.push(self);
+        true
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.technique)?;
+        if !self.description.is_empty() {
+            write!(f, ": {}", self.description)?;
+        }
+
+        let mut changes = Vec::new();
+        for (pos, value) in &self.placements {
+            changes.push(format!("place {value} in {}", cell_name(*pos)));
+        }
+        let mut removed: Vec<u8> = self.eliminations.iter().map(|(_pos, val)| *val).collect();
+        removed.sort();
+        removed.dedup();
+        for value in removed {
+            let cells: Vec<(usize, usize)> = self
+                .eliminations
+                .iter()
+                .filter(|(_pos, val)| *val == value)
+                .map(|(pos, _val)| *pos)
+                .collect();
+            changes.push(format!("remove {value} from {}", cell_names(&cells)));
+        }
+        if !changes.is_empty() {
+            write!(f, " => {}", changes.join("; "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Every step taken while solving a board, in order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SolveLog {
+    pub steps: Vec<Step>,
+}
+
+impl fmt::Display for SolveLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, step) in self.steps.iter().enumerate() {
+            writeln!(f, "{}. {}", index + 1, step)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{
+    als::{handle_als_xy_wing, handle_als_xz, handle_death_blossom},
+    board::{Board, Cell},
+    chains::{handle_aic, handle_x_chain, handle_xy_chain},
+    coloring::{handle_medusa, handle_simple_coloring},
+    config::SolverConfig,
+    fish::{handle_finned_fish, handle_fish},
+    forcing::{handle_cell_forcing_chains, handle_nishio, handle_unit_forcing_chains},
+    house::House,
+    intersections::{handle_blocking_col, handle_blocking_row, handle_pointing},
+    single_digit::{handle_empty_rectangle, handle_skyscraper, handle_two_string_kite},
+    singles::handle_singles,
+    step::{SolveLog, Step},
+    subsets::{handle_hidden_subsets, handle_naked_subsets},
+    sue_de_coq::handle_sue_de_coq,
+    uniqueness::{handle_bug, handle_hidden_unique_rectangle, handle_unique_rectangle},
+    wings::{handle_w_wing, handle_xyz_wing, handle_y_wing},
+};
+
+/// The outcome of applying a strategy to the board once
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepResult {
+    /// The strategy placed values or removed possible values, as described by the steps
+    Updated(Vec<Step>),
+    /// The strategy found nothing to do
+    Unchanged,
+}
+
+/// A solving technique that can be run as part of a `Pipeline`
+pub trait Strategy {
+    /// The name the technique is usually known by
+    fn name(&self) -> &str;
+
+    /// How hard the technique is for a person to spot. Cheaper strategies run first
+    fn difficulty(&self) -> u32;
+
+    /// Runs the technique over the board once
+    fn apply(&self, board: &mut Board) -> StepResult;
+}
+
+/// A function that updates the board, records a step for each change it makes, and returns true
+/// if it changed anything
+type Handler = dyn Fn(&mut Board, &mut Vec<Step>) -> bool;
+
+/// A strategy made from one of the `handle_*` functions, or any other `Handler`
+pub struct Technique {
+    name: &'static str,
+    difficulty: u32,
+    handler: Box<Handler>,
+}
+
+impl Technique {
+    pub fn new(
+        name: &'static str,
+        difficulty: u32,
+        handler: impl Fn(&mut Board, &mut Vec<Step>) -> bool + 'static,
+    ) -> Self {
+        Self {
+            name,
+            difficulty,
+            handler: Box::new(handler),
+        }
+    }
+}
+
+impl Strategy for Technique {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn difficulty(&self) -> u32 {
+        self.difficulty
+    }
+
+    fn apply(&self, board: &mut Board) -> StepResult {
+        let mut steps = Vec::new();
+        if !(self.handler)(board, &mut steps) {
+            return StepResult::Unchanged;
+        }
+        for step in &mut steps {
+            step.technique = self.name.to_string();
+        }
+        StepResult::Updated(steps)
+    }
+}
+
+/// Runs a strategy that works on a single row, col, or group over every one of them. Returns true
+/// if any of them was updated
+fn in_every_house(
+    board: &mut Board,
+    log: &mut Vec<Step>,
+    handler: impl Fn([((usize, usize), &mut Cell); 9], &mut Vec<Step>) -> bool,
+) -> bool {
+    let mut updated = false;
+    for house in House::all() {
+        if handler(board.house_mut(house), log) {
+            updated = true;
+        }
+    }
+    updated
+}
+
+/// An ordered list of strategies. Each step runs the strategies from the start of the list until
+/// one of them updates the board, so after any progress the cheapest strategies are tried again
+/// first. The list can be changed freely to add, disable, or reorder strategies
+pub struct Pipeline {
+    pub strategies: Vec<Box<dyn Strategy>>,
+}
+
+impl Pipeline {
+    /// Returns a pipeline with every built in strategy that `config` allows, cheapest first
+    pub fn new(config: &SolverConfig) -> Self {
+        let mut pipeline = Self::basic();
+
+        for (size, naked, hidden) in [
+            (2, ("Naked Pair", 60), ("Hidden Pair", 70)),
+            (3, ("Naked Triple", 80), ("Hidden Triple", 100)),
+            (4, ("Naked Quad", 120), ("Hidden Quad", 150)),
+        ] {
+            pipeline.add(Technique::new(naked.0, naked.1, move |board, log| {
+                in_every_house(board, log, |cells, log| {
+                    handle_naked_subsets(cells, size, log)
+                })
+            }));
+            pipeline.add(Technique::new(hidden.0, hidden.1, move |board, log| {
+                in_every_house(board, log, |cells, log| {
+                    handle_hidden_subsets(cells, size, log)
+                })
+            }));
+        }
+
+        for (size, fish, finned) in [
+            (2, ("X-Wing", 140), ("Finned X-Wing", 130)),
+            (3, ("Swordfish", 150), ("Finned Swordfish", 150)),
+            (4, ("Jellyfish", 160), ("Finned Jellyfish", 160)),
+        ] {
+            pipeline.add(Technique::new(fish.0, fish.1, move |board, log| {
+                handle_fish(board, size, log)
+            }));
+            pipeline.add(Technique::new(finned.0, finned.1, move |board, log| {
+                handle_finned_fish(board, size, log)
+            }));
+        }
+
+        // Every skyscraper is also a finned X-Wing, so the single digit patterns come before the
+        // finned fish or they would never fire
+        pipeline.add(Technique::new("Skyscraper", 125, handle_skyscraper));
+        pipeline.add(Technique::new("2-String Kite", 125, handle_two_string_kite));
+        pipeline.add(Technique::new(
+            "Empty Rectangle",
+            150,
+            handle_empty_rectangle,
+        ));
+        pipeline.add(Technique::new("Y-Wing", 160, handle_y_wing));
+        pipeline.add(Technique::new("XYZ-Wing", 180, handle_xyz_wing));
+        pipeline.add(Technique::new("W-Wing", 150, handle_w_wing));
+        pipeline.add(Technique::new(
+            "Simple Coloring",
+            150,
+            handle_simple_coloring,
+        ));
+        pipeline.add(Technique::new("3D Medusa", 200, handle_medusa));
+
+        let chain_config = config.clone();
+        pipeline.add(Technique::new("X-Chain", 260, move |board, log| {
+            handle_x_chain(board, &chain_config, log)
+        }));
+        let chain_config = config.clone();
+        pipeline.add(Technique::new("XY-Chain", 260, move |board, log| {
+            handle_xy_chain(board, &chain_config, log)
+        }));
+        let chain_config = config.clone();
+        pipeline.add(Technique::new("AIC", 280, move |board, log| {
+            handle_aic(board, &chain_config, log)
+        }));
+
+        pipeline.add(Technique::new("Sue de Coq", 250, handle_sue_de_coq));
+        pipeline.add(Technique::new("ALS-XZ", 300, handle_als_xz));
+        pipeline.add(Technique::new("ALS-XY-Wing", 320, handle_als_xy_wing));
+        pipeline.add(Technique::new("Death Blossom", 360, handle_death_blossom));
+
+        if config.assume_unique {
+            pipeline.add(Technique::new(
+                "Unique Rectangle",
+                100,
+                handle_unique_rectangle,
+            ));
+            pipeline.add(Technique::new(
+                "Hidden Unique Rectangle",
+                100,
+                handle_hidden_unique_rectangle,
+            ));
+            pipeline.add(Technique::new("BUG+1", 100, handle_bug));
+        }
+
+        // The forcing strategies try values on copies of the board, so they go last
+        let forcing_config = config.clone();
+        pipeline.add(Technique::new("Nishio", 500, move |board, log| {
+            handle_nishio(board, &forcing_config, log)
+        }));
+        let forcing_config = config.clone();
+        pipeline.add(Technique::new(
+            "Cell Forcing Chains",
+            600,
+            move |board, log| handle_cell_forcing_chains(board, &forcing_config, log),
+        ));
+        let forcing_config = config.clone();
+        pipeline.add(Technique::new(
+            "Unit Forcing Chains",
+            600,
+            move |board, log| handle_unit_forcing_chains(board, &forcing_config, log),
+        ));
+
+        pipeline
+    }
+
+    /// Returns a pipeline with only the singles and intersections, which are cheap enough to run
+    /// after every guess
+    pub fn basic() -> Self {
+        let mut pipeline = Self {
+            strategies: Vec::new(),
+        };
+        pipeline.add(Technique::new("Singles", 10, handle_singles));
+        pipeline.add(Technique::new("Pointing", 50, handle_pointing));
+        pipeline.add(Technique::new("Claiming (Rows)", 50, handle_blocking_row));
+        pipeline.add(Technique::new("Claiming (Cols)", 50, handle_blocking_col));
+        pipeline
+    }
+
+    /// Adds a strategy after every strategy that is no harder than it
+    pub fn add(&mut self, strategy: impl Strategy + 'static) {
+        let index = self
+            .strategies
+            .iter()
+            .position(|other| other.difficulty() > strategy.difficulty())
+            .unwrap_or(self.strategies.len());
+        self.strategies.insert(index, Box::new(strategy));
+    }
+
+    /// Removes the strategy with the given name. Returns true if it was in the pipeline
+    pub fn disable(&mut self, name: &str) -> bool {
+        let len = self.strategies.len();
+        self.strategies.retain(|strategy| strategy.name() != name);
+        len != self.strategies.len()
+    }
+
+    /// Returns the difficulty of the strategy with the given name, or None if it isn't in the
+    /// pipeline
+    pub fn difficulty_of(&self, name: &str) -> Option<u32> {
+        self.strategies
+            .iter()
+            .find(|strategy| strategy.name() == name)
+            .map(|strategy| strategy.difficulty())
+    }
+
+    /// Runs the strategies in order until one of them updates the board, and returns the steps
+    /// it took. Returns None if none of them could make progress
+    pub fn step(&self, board: &mut Board) -> Option<Vec<Step>> {
+        self.strategies
+            .iter()
+            .find_map(|strategy| match strategy.apply(board) {
+                StepResult::Updated(steps) => Some(steps),
+                StepResult::Unchanged => None,
+            })
+    }
+
+    /// Steps until the board is solved, contains bad cells, or no strategy can make progress.
+    /// Returns every step taken
+    pub fn run(&self, board: &mut Board) -> SolveLog {
+        let mut log = SolveLog::default();
+        while board.num_unsolved() > 0 && !board.contains_bad_cells() {
+            match self.step(board) {
+                Some(steps) => log.steps.extend(steps),
+                None => break,
+            }
+        }
+        log
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{
+    board::Cell,
+    step::{cell_names, house_name, value_names, Step},
+};
+
+/// Returns every combination of `size` distinct values from `options`, each sorted ascending
+pub fn make_combinations_from_valid_options<T: Copy + Ord>(
+    options: &[T],
+    size: usize,
+) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    let mut options = options.to_vec();
+    options.sort();
+    options.dedup();
+
+    let mut combinations = Vec::new();
+    for (index, first) in options.iter().enumerate() {
+        for mut rest in make_combinations_from_valid_options(&options[index + 1..], size - 1) {
+            rest.insert(0, *first);
+            combinations.push(rest);
+        }
+    }
+    combinations
+}
+
+/// Returns the values in 1..=9 that are not already known in the collection
+fn missing_values(cells: &[((usize, usize), &mut Cell)]) -> Vec<u8> {
+    (1..=9).filter(|val| !is_present(cells, *val)).collect()
+}
+
+/// Returns true if `value` is known in the collection
+fn is_present(cells: &[((usize, usize), &mut Cell)], value: u8) -> bool {
+    cells.iter().any(|(_pos, cell)| cell.value() == Some(value))
+}
+
+/// If the combined possible values of `size` cells in a collection are only `size` values, those
+/// values must be in those cells, so remove them from all other cells in the collection
+/// This strategy is called obvious (naked) pairs, tripples, and quads
+pub fn handle_naked_subsets(
+    mut cells: [((usize, usize), &mut Cell); 9],
+    size: usize,
+    log: &mut Vec<Step>,
+) -> bool {
+    let mut updated = false;
+
+    for subset in make_combinations_from_valid_options(&missing_values(&cells), size) {
+        // A cell may have been solved by an earlier subset, leaving its value as a stale possible
+        // value in the rest of the collection
+        if subset.iter().any(|val| is_present(&cells, *val)) {
+            continue;
+        }
+        // List of all cells whose possible values are limited to the subset
+        let matches: Vec<(usize, usize)> = cells
+            .iter()
+            .filter_map(|(pos, cell)| match cell {
+                Cell::Known(_) => None,
+                Cell::Possible(possible) => {
+                    if !possible.is_empty() && possible.iter().all(|val| subset.contains(&val)) {
+                        Some(*pos)
+                    } else {
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        if matches.len() != size {
+            continue;
+        }
+
+        let mut step = Step::new(
+            format!(
+                "{} can only be {} in {}",
+                cell_names(&matches),
+                value_names(&subset),
+                house_name(&matches)
+            ),
+            matches.clone(),
+            subset.clone(),
+        );
+        for (pos, cell) in &mut cells {
+            if matches.contains(pos) {
+                continue;
+            }
+            for value in &subset {
+                step.remove(*pos, cell, *value);
+            }
+        }
+        if step.record(log) {
+            updated = true;
+        }
+    }
+
+    updated
+}
+
+/// If `size` values in a collection are only possible in the same `size` cells, those cells
+/// must contain those values, so remove all other possible values from those cells
+/// This strategy is called hidden pairs, tripples, and quads
+pub fn handle_hidden_subsets(
+    mut cells: [((usize, usize), &mut Cell); 9],
+    size: usize,
+    log: &mut Vec<Step>,
+) -> bool {
+    let mut updated = false;
+
+    for subset in make_combinations_from_valid_options(&missing_values(&cells), size) {
+        // A cell may have been solved by an earlier subset, leaving its value as a stale possible
+        // value in the rest of the collection
+        if subset.iter().any(|val| is_present(&cells, *val)) {
+            continue;
+        }
+        // List of all cells that have any of the subset as a possible value
+        let matches: Vec<(usize, usize)> = cells
+            .iter()
+            .filter_map(|(pos, cell)| match cell {
+                Cell::Known(_) => None,
+                Cell::Possible(possible) => {
+                    if possible.iter().any(|val| subset.contains(&val)) {
+                        Some(*pos)
+                    } else {
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        if matches.len() != size {
+            continue;
+        }
+
+        // Every value in the subset must still have somewhere to go, otherwise the board is
+        // broken and this isn't a real subset
+        let all_placed = subset.iter().all(|val| {
+            cells.iter().any(|(_pos, cell)| match cell {
+                Cell::Known(_) => false,
+                Cell::Possible(possible) => possible.contains(*val),
+            })
+        });
+        if !all_placed {
+            continue;
+        }
+
+        let mut step = Step::new(
+            format!(
+                "{} are only possible in {} in {}",
+                value_names(&subset),
+                cell_names(&matches),
+                house_name(&matches)
+            ),
+            matches.clone(),
+            subset.clone(),
+        );
+        for (pos, cell) in &mut cells {
+            if !matches.contains(pos) {
+                continue;
+            }
+            let Cell::Possible(possible) = cell else {
+                continue;
+            };
+            let others: Vec<u8> = possible
+                .iter()
+                .filter(|val| !subset.contains(val))
+                .collect();
+            for value in others {
+                step.remove(*pos, cell, value);
+            }
+        }
+        if step.record(log) {
+            updated = true;
+        }
+    }
+
+    updated
+}
@@ -0,0 +1,182 @@
+use crate::{
+    board::{Board, Cell},
+    step::{cell_names, value_names, Step},
+    subsets::make_combinations_from_valid_options,
+};
+
+/// Returns every value possible in the cells, sorted, or None if any of them has been solved by
+/// an earlier elimination
+fn values_of(board: &Board, cells: &[(usize, usize)]) -> Option<Vec<u8>> {
+    let mut values = Vec::new();
+    for (row, col) in cells {
+        match board.get(*row, *col) {
+            Cell::Known(_) => return None,
+            Cell::Possible(possible) => values.extend(possible),
+        }
+    }
+    values.sort();
+    values.dedup();
+    Some(values)
+}
+
+/// Removes every value in `values` from every cell in `house` that isn't in `keep`, recording it
+/// in the step
+fn remove_from_house(
+    board: &mut Board,
+    step: &mut Step,
+    house: &[(usize, usize)],
+    keep: &[(usize, usize)],
+    values: &[u8],
+) {
+    for pos in house.iter().filter(|pos| !keep.contains(pos)) {
+        for value in values {
+            step.eliminate(board, *pos, *value);
+        }
+    }
+}
+
+/// Take 2 or 3 unsolved cells where a group meets a row or col, with at least two more possible
+/// values than cells. Pair them with some cells from the rest of the line and some from the rest
+/// of the group, where the line cells and group cells share no possible values. If all of those
+/// cells together have exactly as many possible values as cells, every value is used exactly
+/// once: the line cells' values stay in the line, the group cells' values stay in the group, and
+/// the rest stay in the intersection. Each value is removed from the other cells of the house it
+/// is locked into
+/// This strategy is called Sue de Coq
+pub fn handle_sue_de_coq(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+
+    for group_row in 0..3 {
+        for group_col in 0..3 {
+            let group: Vec<(usize, usize)> = board
+                .enum_group(group_row, group_col)
+                .iter()
+                .map(|(pos, _cell)| *pos)
+                .collect();
+            let lines = (group_row * 3..group_row * 3 + 3)
+                .map(|row| board.enum_row(row))
+                .chain((group_col * 3..group_col * 3 + 3).map(|col| board.enum_col(col)))
+                .map(|line| line.iter().map(|(pos, _cell)| *pos).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+
+            for line in lines {
+                let unsolved = |cells: &[(usize, usize)]| -> Vec<(usize, usize)> {
+                    cells
+                        .iter()
+                        .filter(|(row, col)| !board.get(*row, *col).is_known())
+                        .copied()
+                        .collect()
+                };
+                let intersection: Vec<(usize, usize)> = unsolved(&line)
+                    .into_iter()
+                    .filter(|pos| group.contains(pos))
+                    .collect();
+                let line_rest: Vec<(usize, usize)> = unsolved(&line)
+                    .into_iter()
+                    .filter(|pos| !group.contains(pos))
+                    .collect();
+                let group_rest: Vec<(usize, usize)> = unsolved(&group)
+                    .into_iter()
+                    .filter(|pos| !line.contains(pos))
+                    .collect();
+
+                for size in 2..=intersection.len() {
+                    for core in make_combinations_from_valid_options(&intersection, size) {
+                        let Some(core_values) = values_of(board, &core) else {
+                            continue;
+                        };
+                        if core_values.len() < size + 2 {
+                            continue;
+                        }
+
+                        for line_size in 1..=line_rest.len().min(3) {
+                            for line_cells in
+                                make_combinations_from_valid_options(&line_rest, line_size)
+                            {
+                                let Some(line_values) = values_of(board, &line_cells) else {
+                                    continue;
+                                };
+                                for group_size in 1..=group_rest.len().min(3) {
+                                    for group_cells in make_combinations_from_valid_options(
+                                        &group_rest,
+                                        group_size,
+                                    ) {
+                                        let Some(group_values) = values_of(board, &group_cells)
+                                        else {
+                                            continue;
+                                        };
+                                        if line_values.iter().any(|val| group_values.contains(val))
+                                        {
+                                            continue;
+                                        }
+                                        let mut all_values = core_values.clone();
+                                        all_values.extend(&line_values);
+                                        all_values.extend(&group_values);
+                                        all_values.sort();
+                                        all_values.dedup();
+                                        if all_values.len() != size + line_size + group_size {
+                                            continue;
+                                        }
+
+                                        // Everything except the group cells' values is locked
+                                        // into the line, and the other way around
+                                        let line_locked: Vec<u8> = all_values
+                                            .iter()
+                                            .filter(|val| !group_values.contains(val))
+                                            .copied()
+                                            .collect();
+                                        let group_locked: Vec<u8> = all_values
+                                            .iter()
+                                            .filter(|val| !line_values.contains(val))
+                                            .copied()
+                                            .collect();
+                                        let mut line_keep = core.clone();
+                                        line_keep.extend(&line_cells);
+                                        let mut group_keep = core.clone();
+                                        group_keep.extend(&group_cells);
+
+                                        let mut cells = core.clone();
+                                        cells.extend(&line_cells);
+                                        cells.extend(&group_cells);
+                                        let mut step = Step::new(
+                                            format!(
+                                                "{} ({}) with {} ({}) and {} ({})",
+                                                cell_names(&core),
+                                                value_names(&core_values),
+                                                cell_names(&line_cells),
+                                                value_names(&line_values),
+                                                cell_names(&group_cells),
+                                                value_names(&group_values)
+                                            ),
+                                            cells,
+                                            all_values.clone(),
+                                        );
+                                        remove_from_house(
+                                            board,
+                                            &mut step,
+                                            &line,
+                                            &line_keep,
+                                            &line_locked,
+                                        );
+                                        remove_from_house(
+                                            board,
+                                            &mut step,
+                                            &group,
+                                            &group_keep,
+                                            &group_locked,
+                                        );
+                                        if step.record(log) {
+                                            updated = true;
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    updated
+}
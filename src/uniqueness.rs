@@ -0,0 +1,337 @@
+use crate::{
+    board::{Board, Cell},
+    house::House,
+    step::{cell_name, cell_names, value_names, Step},
+    subsets::make_combinations_from_valid_options,
+};
+
+/// The corners of a rectangle, in the order top left, top right, bottom left, bottom right
+type Corners = [(usize, usize); 4];
+
+/// Two corners of a rectangle that share a row or col
+type Side = [(usize, usize); 2];
+
+/// Returns the positions of every row, col, and group that contains both `a` and `b`
+fn shared_houses(a: (usize, usize), b: (usize, usize)) -> Vec<&'static [(usize, usize); 9]> {
+    Board::houses()
+        .filter(|house| house.contains(&a) && house.contains(&b))
+        .collect()
+}
+
+/// Returns the corners of every rectangle of unsolved cells that spans two rows, two cols, and
+/// two groups, along with each pair of values possible in all four corners
+fn rectangles(board: &Board) -> Vec<(Corners, (u8, u8))> {
+    let mut rectangles = Vec::new();
+    for r1 in 0..9 {
+        for r2 in r1 + 1..9 {
+            for c1 in 0..9 {
+                for c2 in c1 + 1..9 {
+                    if (r1 / 3 == r2 / 3) == (c1 / 3 == c2 / 3) {
+                        continue;
+                    }
+                    let corners = [(r1, c1), (r1, c2), (r2, c1), (r2, c2)];
+                    let common: Vec<u8> = (1..=9)
+                        .filter(|val| {
+                            corners
+                                .iter()
+                                .all(|(row, col)| board.get(*row, *col).is_possible(*val))
+                        })
+                        .collect();
+                    for pair in make_combinations_from_valid_options(&common, 2) {
+                        rectangles.push((corners, (pair[0], pair[1])));
+                    }
+                }
+            }
+        }
+    }
+    rectangles
+}
+
+/// Returns true if both values are still possible in all four corners, since earlier
+/// eliminations may have broken a rectangle
+fn is_intact(board: &Board, corners: &Corners, (a, b): (u8, u8)) -> bool {
+    corners.iter().all(|(row, col)| {
+        let cell = board.get(*row, *col);
+        cell.is_possible(a) && cell.is_possible(b)
+    })
+}
+
+/// Returns a step for a unique rectangle, described by the given type
+fn rectangle_step(corners: &Corners, (a, b): (u8, u8), kind: &str) -> Step {
+    Step::new(
+        format!(
+            "{} would form a deadly pattern of {a} and {b} ({kind})",
+            cell_names(corners)
+        ),
+        corners.to_vec(),
+        vec![a, b],
+    )
+}
+
+/// Returns the pairs of corners that share a row or col, as `(floor, roof)` where the floor is
+/// one side of the rectangle and the roof is the opposite side
+fn sides(corners: &Corners) -> [(Side, Side); 4] {
+    let [top_left, top_right, bottom_left, bottom_right] = *corners;
+    [
+        ([top_left, top_right], [bottom_left, bottom_right]),
+        ([bottom_left, bottom_right], [top_left, top_right]),
+        ([top_left, bottom_left], [top_right, bottom_right]),
+        ([top_right, bottom_right], [top_left, bottom_left]),
+    ]
+}
+
+/// If four cells of a rectangle across two groups could all only be the same two values, the
+/// values could be swapped to give two solutions. A puzzle with a unique solution can't allow
+/// that, so at least one corner must be something else:
+/// * Type 1: three corners are the pair, so remove the pair from the fourth
+/// * Type 2: the two roof corners have the same single extra value, so one of them must be it,
+///   and it is removed from every cell that sees both
+/// * Type 3: the extra values of the roof corners form a naked subset with other cells in a
+///   house they share, so those values are removed from the rest of the house
+/// * Type 4: one value of the pair is only possible in the roof corners of a house they share,
+///   so the other value is removed from both roof corners
+///
+/// This strategy is called Unique Rectangle, and is only valid if the puzzle has one solution
+pub fn handle_unique_rectangle(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+
+    for (corners, (a, b)) in rectangles(board) {
+        if !is_intact(board, &corners, (a, b)) {
+            continue;
+        }
+        let is_pair =
+            |board: &Board, (row, col): (usize, usize)| board.get(row, col).possible_count() == 2;
+
+        // Type 1
+        let extras: Vec<(usize, usize)> = corners
+            .iter()
+            .filter(|pos| !is_pair(board, **pos))
+            .copied()
+            .collect();
+        if extras.len() == 1 {
+            let mut step = rectangle_step(&corners, (a, b), "type 1");
+            step.eliminate(board, extras[0], a);
+            step.eliminate(board, extras[0], b);
+            if step.record(log) {
+                updated = true;
+            }
+            continue;
+        }
+
+        for (floor, roof) in sides(&corners) {
+            if !is_intact(board, &corners, (a, b))
+                || !floor.iter().all(|pos| is_pair(board, *pos))
+                || roof.iter().any(|pos| is_pair(board, *pos))
+            {
+                continue;
+            }
+            let mut roof_extras: Vec<u8> = roof
+                .iter()
+                .flat_map(|(row, col)| match board.get(*row, *col) {
+                    Cell::Known(_) => Vec::new(),
+                    Cell::Possible(values) => values.to_vec(),
+                })
+                .filter(|val| *val != a && *val != b)
+                .collect();
+            roof_extras.sort();
+            roof_extras.dedup();
+
+            // Type 2
+            let single_extra = roof
+                .iter()
+                .all(|(row, col)| board.get(*row, *col).possible_count() == 3);
+            if single_extra && roof_extras.len() == 1 {
+                let mut step = rectangle_step(&corners, (a, b), "type 2");
+                for &pos in Board::peers(roof[0].0, roof[0].1) {
+                    if Board::sees(roof[1], pos) {
+                        step.eliminate(board, pos, roof_extras[0]);
+                    }
+                }
+                if step.record(log) {
+                    updated = true;
+                }
+            }
+
+            for house in shared_houses(roof[0], roof[1]) {
+                let others: Vec<(usize, usize)> = house
+                    .iter()
+                    .filter(|pos| !roof.contains(pos) && !board.get(pos.0, pos.1).is_known())
+                    .copied()
+                    .collect();
+
+                // Type 3
+                for size in 1..=3 {
+                    for subset in make_combinations_from_valid_options(&others, size) {
+                        // Cells may have been solved by an earlier elimination
+                        if subset
+                            .iter()
+                            .any(|(row, col)| board.get(*row, *col).is_known())
+                        {
+                            continue;
+                        }
+                        let mut values = roof_extras.clone();
+                        for (row, col) in &subset {
+                            if let Cell::Possible(possible) = board.get(*row, *col) {
+                                values.extend(possible);
+                            }
+                        }
+                        values.sort();
+                        values.dedup();
+                        if values.len() != size + 1 {
+                            continue;
+                        }
+                        let mut step = rectangle_step(&corners, (a, b), "type 3");
+                        step.description += &format!(
+                            ", so {} and {} form a subset of {}",
+                            cell_names(&roof),
+                            cell_names(&subset),
+                            value_names(&values)
+                        );
+                        step.cells.extend(&subset);
+                        for pos in others.iter().filter(|pos| !subset.contains(pos)) {
+                            for value in &values {
+                                step.eliminate(board, *pos, *value);
+                            }
+                        }
+                        if step.record(log) {
+                            updated = true;
+                        }
+                    }
+                }
+
+                // Type 4
+                for (locked, other) in [(a, b), (b, a)] {
+                    // If the value is already known in the house the roof can't be locked to it
+                    let only_in_roof = house.iter().all(|(row, col)| {
+                        roof.contains(&(*row, *col)) || {
+                            let cell = board.get(*row, *col);
+                            !cell.is_possible(locked) && cell.value() != Some(locked)
+                        }
+                    });
+                    if only_in_roof {
+                        let mut step = rectangle_step(&corners, (a, b), "type 4");
+                        for pos in roof {
+                            step.eliminate(board, pos, other);
+                        }
+                        if step.record(log) {
+                            updated = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    updated
+}
+
+/// If one corner of a unique rectangle is only the pair {a, b}, and in both the row and col of
+/// the opposite corner `a` is only possible in the rectangle, then the opposite corner being `b`
+/// would force the deadly pattern, so `b` is removed from it
+/// This strategy is called Hidden Unique Rectangle, and is only valid if the puzzle has one
+/// solution
+pub fn handle_hidden_unique_rectangle(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+
+    for (corners, (a, b)) in rectangles(board) {
+        if !is_intact(board, &corners, (a, b)) {
+            continue;
+        }
+        // Pair each corner with the one diagonally opposite it
+        for (corner, opposite) in [(0, 3), (3, 0), (1, 2), (2, 1)] {
+            let (row, col) = corners[corner];
+            if !is_intact(board, &corners, (a, b)) || board.get(row, col).possible_count() != 2 {
+                continue;
+            }
+            let (opposite_row, opposite_col) = corners[opposite];
+            for (locked, other) in [(a, b), (b, a)] {
+                if !is_intact(board, &corners, (a, b)) {
+                    continue;
+                }
+                let only_in_rectangle = |cells: [((usize, usize), &Cell); 9]| {
+                    cells
+                        .iter()
+                        .filter(|(_pos, cell)| {
+                            cell.is_possible(locked) || cell.value() == Some(locked)
+                        })
+                        .all(|(pos, _cell)| corners.contains(pos))
+                };
+                if only_in_rectangle(board.enum_row(opposite_row))
+                    && only_in_rectangle(board.enum_col(opposite_col))
+                {
+                    let mut step = rectangle_step(&corners, (a, b), "hidden");
+                    step.eliminate(board, (opposite_row, opposite_col), other);
+                    if step.record(log) {
+                        updated = true;
+                    }
+                }
+            }
+        }
+    }
+
+    updated
+}
+
+/// If every unsolved cell has two possible values except one cell with three, and every possible
+/// value appears exactly twice in each row, col, and group apart from the extra value, then
+/// without the extra value the puzzle would have either no solution or two of them. So the cell
+/// must be the value that appears three times in its houses
+/// This strategy is called Bivalue Universal Grave (BUG+1), and is only valid if the puzzle has
+/// one solution
+pub fn handle_bug(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut extra = None;
+    for row in 0..9 {
+        for col in 0..9 {
+            match board.get(row, col).possible_count() {
+                0 | 2 => {}
+                3 if extra.is_none() => extra = Some((row, col)),
+                _ => return false,
+            }
+        }
+    }
+    let Some((row, col)) = extra else {
+        return false;
+    };
+
+    let mut bug_value = None;
+    for house in House::all().map(|house| board.house(house)) {
+        let is_bug_house = house.iter().any(|(pos, _cell)| *pos == (row, col));
+        for value in 1..=9 {
+            let known = house.iter().any(|(_pos, cell)| cell.value() == Some(value));
+            let count = house
+                .iter()
+                .filter(|(_pos, cell)| cell.is_possible(value))
+                .count();
+            // A possible value that is already known in the house would break the pattern
+            if known && count > 0 {
+                return false;
+            }
+            match count {
+                0 | 2 => {}
+                3 if is_bug_house => {
+                    if bug_value.is_some_and(|bug_value| bug_value != value) {
+                        return false;
+                    }
+                    bug_value = Some(value);
+                }
+                _ => return false,
+            }
+        }
+    }
+
+    match bug_value {
+        Some(value) if board.get(row, col).is_possible(value) => {
+            let mut step = Step::new(
+                format!(
+                    "every other cell has two possible values, so {} must be {value}",
+                    cell_name((row, col))
+                ),
+                vec![(row, col)],
+                vec![value],
+            );
+            step.place((row, col), board.get_mut(row, col), value);
+            step.record(log)
+        }
+        _ => false,
+    }
+}
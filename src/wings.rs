@@ -0,0 +1,145 @@
+use crate::{
+    board::Board,
+    candidates::Candidates,
+    step::{cell_name, cell_names, value_names, Step},
+};
+
+/// If a pivot cell with possible values {x, y} sees a pincer with {x, z} and a pincer with
+/// {y, z}, then one of the pincers must be z, so remove z from every cell that sees both pincers
+/// This strategy is called Y-Wing or XY-Wing
+pub fn handle_y_wing(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+    let bivalues = board.cells_with_possible_count(2);
+
+    for (pivot, pivot_values) in &bivalues {
+        let Some((x, y)) = pivot_values.pair() else {
+            continue;
+        };
+        let pincers: Vec<&((usize, usize), Candidates)> = bivalues
+            .iter()
+            .filter(|(pos, _values)| Board::sees(*pivot, *pos))
+            .collect();
+
+        for (a, a_values) in &pincers {
+            if !a_values.contains(x) || a_values.contains(y) {
+                continue;
+            }
+            let Some(z) = (*a_values - Candidates::single(x)).single_value() else {
+                continue;
+            };
+            for (b, b_values) in &pincers {
+                if a == b || !b_values.contains(y) || !b_values.contains(z) {
+                    continue;
+                }
+                let mut step = Step::new(
+                    format!(
+                        "{} is {} or {}, so {z} must be in {} or {}",
+                        cell_name(*pivot),
+                        x,
+                        y,
+                        cell_name(*a),
+                        cell_name(*b)
+                    ),
+                    vec![*pivot, *a, *b],
+                    vec![x, y, z],
+                );
+                step.eliminate_from_common_peers(board, &[*a, *b], z);
+                if step.record(log) {
+                    updated = true;
+                }
+            }
+        }
+    }
+
+    updated
+}
+
+/// If a pivot cell with possible values {x, y, z} sees a pincer with {x, z} and a pincer with
+/// {y, z}, then one of the three cells must be z, so remove z from every cell that sees all three
+/// This strategy is called XYZ-Wing
+pub fn handle_xyz_wing(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+    let bivalues = board.cells_with_possible_count(2);
+
+    for (pivot, pivot_values) in board.cells_with_possible_count(3) {
+        let pincers: Vec<&((usize, usize), Candidates)> = bivalues
+            .iter()
+            .filter(|(pos, values)| Board::sees(pivot, *pos) && values.is_subset(pivot_values))
+            .collect();
+
+        for (index, (a, a_values)) in pincers.iter().enumerate() {
+            for (b, b_values) in &pincers[index + 1..] {
+                // The pincers must share exactly one value, and cover the pivot between them
+                let Some(shared) = (*a_values & *b_values).single_value() else {
+                    continue;
+                };
+                let mut step = Step::new(
+                    format!(
+                        "{} is {}, so {} must be in {}",
+                        cell_name(pivot),
+                        value_names(&pivot_values.to_vec()),
+                        shared,
+                        cell_names(&[pivot, *a, *b])
+                    ),
+                    vec![pivot, *a, *b],
+                    pivot_values.to_vec(),
+                );
+                step.eliminate_from_common_peers(board, &[pivot, *a, *b], shared);
+                if step.record(log) {
+                    updated = true;
+                }
+            }
+        }
+    }
+
+    updated
+}
+
+/// If two cells that can't see each other both have possible values {x, y}, and there is a
+/// conjugate pair on x where one end sees the first cell and the other end sees the second, then
+/// one of the two cells must be y, so remove y from every cell that sees both of them
+/// This strategy is called W-Wing
+pub fn handle_w_wing(board: &mut Board, log: &mut Vec<Step>) -> bool {
+    let mut updated = false;
+    let bivalues = board.cells_with_possible_count(2);
+
+    for (index, (a, a_values)) in bivalues.iter().enumerate() {
+        for (b, b_values) in &bivalues[index + 1..] {
+            if a_values != b_values || Board::sees(*a, *b) {
+                continue;
+            }
+            let Some((first, second)) = a_values.pair() else {
+                continue;
+            };
+            for (x, y) in [(first, second), (second, first)] {
+                let link = board.conjugate_pairs(x).into_iter().find(|(c, d)| {
+                    let ends = [*a, *b];
+                    !ends.contains(c)
+                        && !ends.contains(d)
+                        && ((Board::sees(*a, *c) && Board::sees(*b, *d))
+                            || (Board::sees(*a, *d) && Board::sees(*b, *c)))
+                });
+                let Some((c, d)) = link else {
+                    continue;
+                };
+                let mut step = Step::new(
+                    format!(
+                        "{x} must be in {} or {}, so {y} must be in {} or {}",
+                        cell_name(c),
+                        cell_name(d),
+                        cell_name(*a),
+                        cell_name(*b)
+                    ),
+                    vec![*a, *b, c, d],
+                    vec![x, y],
+                );
+                step.eliminate_from_common_peers(board, &[*a, *b], y);
+                if step.record(log) {
+                    updated = true;
+                }
+            }
+        }
+    }
+
+    updated
+}